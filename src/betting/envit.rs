@@ -0,0 +1,308 @@
+use scorers::{ali, flor, secansa};
+use std::marker::PhantomData;
+use Error;
+use Result;
+use Team;
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq, PartialOrd, Ord)]
+pub enum EnvitLevel {
+    Announced,
+    Envit,
+    // e.g.: Val(3) is tres val
+    Val(u8),
+    Resto,
+}
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum EnvitAction {
+    Announce,
+    Raise(EnvitLevel),
+    Accept,
+    Reject,
+}
+
+pub trait EnvitBet: Sized {
+    fn is_legal(level: EnvitLevel) -> bool;
+    // Winner is only set when the next raise was rejected
+    fn from_level(level: EnvitLevel, winner: Option<Team>) -> Option<Self>;
+}
+
+impl EnvitBet for flor::AgreedBet {
+    fn is_legal(level: EnvitLevel) -> bool {
+        match level {
+            EnvitLevel::Announced | EnvitLevel::Envit | EnvitLevel::Resto => true,
+            EnvitLevel::Val(_) => false,
+        }
+    }
+
+    fn from_level(level: EnvitLevel, winner: Option<Team>) -> Option<Self> {
+        match level {
+            EnvitLevel::Announced => Some(flor::AgreedBet::Announced(winner)),
+            EnvitLevel::Envit => Some(flor::AgreedBet::Envit(winner)),
+            EnvitLevel::Resto => Some(flor::AgreedBet::Resto),
+            EnvitLevel::Val(_) => None,
+        }
+    }
+}
+
+impl EnvitBet for secansa::AgreedBet {
+    fn is_legal(level: EnvitLevel) -> bool {
+        match level {
+            EnvitLevel::Announced | EnvitLevel::Envit => true,
+            EnvitLevel::Val(extra) => extra >= 3,
+            EnvitLevel::Resto => false,
+        }
+    }
+
+    fn from_level(level: EnvitLevel, winner: Option<Team>) -> Option<Self> {
+        match level {
+            EnvitLevel::Announced => Some(secansa::AgreedBet::Announced(winner)),
+            EnvitLevel::Envit => Some(secansa::AgreedBet::Envit(winner)),
+            EnvitLevel::Val(extra) => Some(secansa::AgreedBet::Val(extra, winner)),
+            EnvitLevel::Resto => None,
+        }
+    }
+}
+
+impl EnvitBet for ali::AgreedBet {
+    fn is_legal(level: EnvitLevel) -> bool {
+        secansa::AgreedBet::is_legal(level)
+    }
+
+    fn from_level(level: EnvitLevel, winner: Option<Team>) -> Option<Self> {
+        match level {
+            EnvitLevel::Announced => Some(ali::AgreedBet::Announced(winner)),
+            EnvitLevel::Envit => Some(ali::AgreedBet::Envit(winner)),
+            EnvitLevel::Val(extra) => Some(ali::AgreedBet::Val(extra, winner)),
+            EnvitLevel::Resto => None,
+        }
+    }
+}
+
+pub type FlorNegotiation = EnvitNegotiation<flor::AgreedBet>;
+pub type SecansaNegotiation = EnvitNegotiation<secansa::AgreedBet>;
+pub type AliNegotiation = EnvitNegotiation<ali::AgreedBet>;
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub struct EnvitNegotiation<B> {
+    // Last level both teams agreed on
+    agreed: Option<EnvitLevel>,
+    // Level waiting for an answer, along with the team that proposed it
+    pending: Option<(Team, EnvitLevel)>,
+    winner: Option<Team>,
+    closed: bool,
+    bet: PhantomData<B>,
+}
+
+impl<B> Default for EnvitNegotiation<B> {
+    fn default() -> Self {
+        EnvitNegotiation {
+            agreed: None,
+            pending: None,
+            winner: None,
+            closed: false,
+            bet: PhantomData,
+        }
+    }
+}
+
+impl<B: EnvitBet> EnvitNegotiation<B> {
+    pub fn to_speak(&self) -> Option<Team> {
+        //! The team that has to accept, reject or raise the last proposal, if any.
+
+        self.pending.map(|(proposer, _)| proposer.opponent())
+    }
+
+    pub fn is_closed(&self) -> bool {
+        self.closed
+    }
+
+    pub fn agreed_bet(&self) -> Option<B> {
+        //! The outcome of the negotiation, once it is closed.
+
+        if !self.closed {
+            return None;
+        }
+        B::from_level(self.agreed?, self.winner)
+    }
+
+    pub fn can_raise(&self, team: Team, level: EnvitLevel) -> bool {
+        match self.pending {
+            Some((_, current)) => {
+                self.to_speak() == Some(team) && level > current && B::is_legal(level)
+            }
+            None => false,
+        }
+    }
+
+    pub fn apply(&mut self, team: Team, action: EnvitAction) -> Result<()> {
+        match action {
+            EnvitAction::Announce => self.announce(team),
+            EnvitAction::Raise(level) => self.raise(team, level),
+            EnvitAction::Accept => self.accept(team),
+            EnvitAction::Reject => self.reject(team),
+        }
+    }
+
+    pub fn announce(&mut self, team: Team) -> Result<()> {
+        if self.closed || self.pending.is_some() {
+            return Err(Error::IllegalBet);
+        }
+        self.pending = Some((team, EnvitLevel::Announced));
+        Ok(())
+    }
+
+    pub fn raise(&mut self, team: Team, level: EnvitLevel) -> Result<()> {
+        //! Raising accepts the pending proposal first.
+
+        if !self.can_raise(team, level) {
+            return Err(Error::IllegalBet);
+        }
+        self.agreed = self.pending.map(|(_, current)| current);
+        self.pending = Some((team, level));
+        Ok(())
+    }
+
+    pub fn accept(&mut self, team: Team) -> Result<()> {
+        let (_, level) = self.take_pending(team)?;
+        self.agreed = Some(level);
+        self.closed = true;
+        Ok(())
+    }
+
+    pub fn reject(&mut self, team: Team) -> Result<()> {
+        //! Rejecting a raise gives the game to the team that raised, at the last agreed level.
+
+        match self.pending {
+            Some((_, EnvitLevel::Announced)) | None => return Err(Error::IllegalBet),
+            _ => (),
+        }
+        let (proposer, _) = self.take_pending(team)?;
+        self.winner = Some(proposer);
+        self.closed = true;
+        Ok(())
+    }
+
+    fn take_pending(&mut self, team: Team) -> Result<(Team, EnvitLevel)> {
+        if self.to_speak() != Some(team) {
+            return Err(Error::IllegalBet);
+        }
+        self.pending.take().ok_or(Error::IllegalBet)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn not_announced() {
+        let negotiation = SecansaNegotiation::default();
+        assert_eq!(negotiation.to_speak(), None);
+        assert_eq!(negotiation.agreed_bet(), None);
+    }
+
+    #[test]
+    fn accept_announced() {
+        let mut negotiation = SecansaNegotiation::default();
+        negotiation.announce(Team::Team1).unwrap();
+        assert_eq!(negotiation.to_speak(), Some(Team::Team2));
+        assert_eq!(negotiation.agreed_bet(), None);
+
+        assert_eq!(negotiation.accept(Team::Team1), Err(Error::IllegalBet));
+        assert_eq!(negotiation.reject(Team::Team2), Err(Error::IllegalBet));
+        negotiation.accept(Team::Team2).unwrap();
+
+        assert!(negotiation.is_closed());
+        assert_eq!(
+            negotiation.agreed_bet(),
+            Some(secansa::AgreedBet::Announced(None))
+        );
+        assert_eq!(negotiation.announce(Team::Team1), Err(Error::IllegalBet));
+    }
+
+    #[test]
+    fn accept_envit() {
+        let mut negotiation = AliNegotiation::default();
+        negotiation.announce(Team::Team1).unwrap();
+        negotiation.raise(Team::Team2, EnvitLevel::Envit).unwrap();
+        assert_eq!(negotiation.to_speak(), Some(Team::Team1));
+        negotiation.accept(Team::Team1).unwrap();
+
+        assert_eq!(negotiation.agreed_bet(), Some(ali::AgreedBet::Envit(None)));
+    }
+
+    #[test]
+    fn reject_envit() {
+        let mut negotiation = AliNegotiation::default();
+        negotiation.announce(Team::Team1).unwrap();
+        negotiation.raise(Team::Team2, EnvitLevel::Envit).unwrap();
+        negotiation.reject(Team::Team1).unwrap();
+
+        assert_eq!(
+            negotiation.agreed_bet(),
+            Some(ali::AgreedBet::Announced(Some(Team::Team2)))
+        );
+    }
+
+    #[test]
+    fn raise_vals() {
+        let mut negotiation = SecansaNegotiation::default();
+        negotiation
+            .apply(Team::Team2, EnvitAction::Announce)
+            .unwrap();
+        negotiation
+            .apply(Team::Team1, EnvitAction::Raise(EnvitLevel::Envit))
+            .unwrap();
+
+        // Only the team that has to speak can raise, and only higher and to legal levels
+        assert!(!negotiation.can_raise(Team::Team1, EnvitLevel::Val(3)));
+        assert!(!negotiation.can_raise(Team::Team2, EnvitLevel::Envit));
+        assert!(!negotiation.can_raise(Team::Team2, EnvitLevel::Val(2)));
+        assert!(!negotiation.can_raise(Team::Team2, EnvitLevel::Resto));
+        assert!(negotiation.can_raise(Team::Team2, EnvitLevel::Val(3)));
+
+        negotiation
+            .apply(Team::Team2, EnvitAction::Raise(EnvitLevel::Val(3)))
+            .unwrap();
+        negotiation
+            .apply(Team::Team1, EnvitAction::Raise(EnvitLevel::Val(5)))
+            .unwrap();
+        assert_eq!(
+            negotiation.apply(Team::Team2, EnvitAction::Raise(EnvitLevel::Val(4))),
+            Err(Error::IllegalBet)
+        );
+        negotiation.apply(Team::Team2, EnvitAction::Reject).unwrap();
+
+        assert_eq!(
+            negotiation.agreed_bet(),
+            Some(secansa::AgreedBet::Val(3, Some(Team::Team1)))
+        );
+    }
+
+    #[test]
+    fn flor_resto() {
+        let mut negotiation = FlorNegotiation::default();
+        negotiation.announce(Team::Team1).unwrap();
+        assert!(!negotiation.can_raise(Team::Team2, EnvitLevel::Val(3)));
+        negotiation.raise(Team::Team2, EnvitLevel::Envit).unwrap();
+        negotiation.raise(Team::Team1, EnvitLevel::Resto).unwrap();
+        negotiation.accept(Team::Team2).unwrap();
+
+        assert_eq!(negotiation.agreed_bet(), Some(flor::AgreedBet::Resto));
+    }
+
+    #[test]
+    fn flor_reject_resto() {
+        let mut negotiation = FlorNegotiation::default();
+        negotiation.announce(Team::Team1).unwrap();
+        negotiation.raise(Team::Team2, EnvitLevel::Envit).unwrap();
+        negotiation.raise(Team::Team1, EnvitLevel::Resto).unwrap();
+        negotiation.reject(Team::Team2).unwrap();
+
+        assert_eq!(
+            negotiation.agreed_bet(),
+            Some(flor::AgreedBet::Envit(Some(Team::Team1)))
+        );
+    }
+}
@@ -0,0 +1,2 @@
+pub mod envit;
+pub mod truc;
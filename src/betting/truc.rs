@@ -0,0 +1,185 @@
+use scorers::truc::Bet;
+use Error;
+use Result;
+use Team;
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum TrucAction {
+    // Call truc, or raise a previous call to retruc or nou val
+    Call,
+    Accept,
+    Reject,
+}
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Default)]
+pub struct TrucNegotiation {
+    agreed_bet: Bet,
+    // Call waiting for an answer, along with the team that made it
+    pending: Option<(Team, Bet)>,
+    last_caller: Option<Team>,
+}
+
+impl TrucNegotiation {
+    pub fn bet(&self) -> Bet {
+        self.agreed_bet
+    }
+
+    pub fn awaiting_response(&self) -> Option<Team> {
+        //! The team that has to accept, reject or raise the last call, if any.
+
+        self.pending.map(|(caller, _)| caller.opponent())
+    }
+
+    pub fn is_closed(&self) -> bool {
+        self.agreed_bet.winner().is_some() || self.agreed_bet == Bet::NouVal(None)
+    }
+
+    pub fn can_call(&self, team: Team) -> bool {
+        //! Anyone can call truc, but only the team that was challenged can raise it.
+
+        if self.is_closed() {
+            return false;
+        }
+        match self.pending {
+            Some((caller, proposed)) => caller != team && proposed.raise().is_some(),
+            None => self.last_caller != Some(team) && self.agreed_bet.raise().is_some(),
+        }
+    }
+
+    pub fn apply(&mut self, team: Team, action: TrucAction) -> Result<()> {
+        match action {
+            TrucAction::Call => self.call(team),
+            TrucAction::Accept => self.accept(team),
+            TrucAction::Reject => self.reject(team),
+        }
+    }
+
+    pub fn call(&mut self, team: Team) -> Result<()> {
+        //! Raising on a pending call accepts it first.
+
+        if !self.can_call(team) {
+            return Err(Error::IllegalBet);
+        }
+        if let Some((_, proposed)) = self.pending.take() {
+            self.agreed_bet = proposed;
+        }
+        let raised = self.agreed_bet.raise().ok_or(Error::IllegalBet)?;
+        self.pending = Some((team, raised));
+        self.last_caller = Some(team);
+        Ok(())
+    }
+
+    pub fn accept(&mut self, team: Team) -> Result<()> {
+        let (_, proposed) = self.take_pending(team)?;
+        self.agreed_bet = proposed;
+        Ok(())
+    }
+
+    pub fn reject(&mut self, team: Team) -> Result<()> {
+        let (caller, proposed) = self.take_pending(team)?;
+        self.agreed_bet = proposed.decline(caller);
+        Ok(())
+    }
+
+    fn take_pending(&mut self, team: Team) -> Result<(Team, Bet)> {
+        if self.awaiting_response() != Some(team) {
+            return Err(Error::IllegalBet);
+        }
+        self.pending.take().ok_or(Error::IllegalBet)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn accept_truc() {
+        let mut negotiation = TrucNegotiation::default();
+        assert_eq!(negotiation.bet(), Bet::None);
+
+        negotiation.call(Team::Team1).unwrap();
+        assert_eq!(negotiation.awaiting_response(), Some(Team::Team2));
+        assert_eq!(negotiation.bet(), Bet::None);
+
+        negotiation.accept(Team::Team2).unwrap();
+        assert_eq!(negotiation.awaiting_response(), None);
+        assert_eq!(negotiation.bet(), Bet::Truc(None));
+    }
+
+    #[test]
+    fn reject_truc() {
+        let mut negotiation = TrucNegotiation::default();
+        negotiation.call(Team::Team2).unwrap();
+        negotiation.reject(Team::Team1).unwrap();
+
+        assert_eq!(negotiation.bet(), Bet::Truc(Some(Team::Team2)));
+        assert!(negotiation.is_closed());
+        assert_eq!(negotiation.call(Team::Team1), Err(Error::IllegalBet));
+    }
+
+    #[test]
+    fn only_challenged_team_answers() {
+        let mut negotiation = TrucNegotiation::default();
+        negotiation.call(Team::Team1).unwrap();
+
+        assert_eq!(negotiation.accept(Team::Team1), Err(Error::IllegalBet));
+        assert_eq!(negotiation.reject(Team::Team1), Err(Error::IllegalBet));
+        assert_eq!(negotiation.call(Team::Team1), Err(Error::IllegalBet));
+        assert_eq!(negotiation.awaiting_response(), Some(Team::Team2));
+    }
+
+    #[test]
+    fn nothing_to_answer() {
+        let mut negotiation = TrucNegotiation::default();
+        assert_eq!(negotiation.accept(Team::Team1), Err(Error::IllegalBet));
+        assert_eq!(negotiation.reject(Team::Team2), Err(Error::IllegalBet));
+    }
+
+    #[test]
+    fn only_challenged_team_raises() {
+        let mut negotiation = TrucNegotiation::default();
+        negotiation.call(Team::Team1).unwrap();
+        negotiation.accept(Team::Team2).unwrap();
+
+        assert!(!negotiation.can_call(Team::Team1));
+        assert_eq!(negotiation.call(Team::Team1), Err(Error::IllegalBet));
+
+        assert!(negotiation.can_call(Team::Team2));
+        negotiation.call(Team::Team2).unwrap();
+        negotiation.accept(Team::Team1).unwrap();
+        assert_eq!(negotiation.bet(), Bet::Retruc(None));
+
+        assert_eq!(negotiation.call(Team::Team2), Err(Error::IllegalBet));
+        negotiation.call(Team::Team1).unwrap();
+        negotiation.accept(Team::Team2).unwrap();
+        assert_eq!(negotiation.bet(), Bet::NouVal(None));
+
+        assert!(negotiation.is_closed());
+        assert_eq!(negotiation.call(Team::Team2), Err(Error::IllegalBet));
+    }
+
+    #[test]
+    fn raise_accepts_pending_call() {
+        let mut negotiation = TrucNegotiation::default();
+        negotiation.call(Team::Team1).unwrap();
+        negotiation.call(Team::Team2).unwrap();
+
+        assert_eq!(negotiation.bet(), Bet::Truc(None));
+        assert_eq!(negotiation.awaiting_response(), Some(Team::Team1));
+
+        negotiation.call(Team::Team1).unwrap();
+        negotiation.reject(Team::Team2).unwrap();
+        assert_eq!(negotiation.bet(), Bet::NouVal(Some(Team::Team1)));
+    }
+
+    #[test]
+    fn reject_raise() {
+        let mut negotiation = TrucNegotiation::default();
+        negotiation.apply(Team::Team1, TrucAction::Call).unwrap();
+        negotiation.apply(Team::Team2, TrucAction::Call).unwrap();
+        negotiation.apply(Team::Team1, TrucAction::Reject).unwrap();
+
+        assert_eq!(negotiation.bet(), Bet::Retruc(Some(Team::Team2)));
+    }
+}
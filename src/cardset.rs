@@ -0,0 +1,227 @@
+use deck::{self, Card, SUITS, VALUES};
+use std::iter::FromIterator;
+use std::ops::{BitAnd, BitOr, Not, Sub};
+
+const ALL: u64 = (1 << 40) - 1;
+
+// A set of cards from the 40-card deck, one bit per card in deck order
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash, Default)]
+pub struct CardSet(u64);
+
+impl CardSet {
+    pub fn empty() -> Self {
+        CardSet(0)
+    }
+
+    pub fn full() -> Self {
+        CardSet(ALL)
+    }
+
+    pub fn len(self) -> usize {
+        self.0.count_ones() as usize
+    }
+
+    pub fn is_empty(self) -> bool {
+        self.0 == 0
+    }
+
+    pub fn contains(self, card: Card) -> bool {
+        self.0 & bit(card) != 0
+    }
+
+    pub fn insert(&mut self, card: Card) -> bool {
+        //! Returns whether the card was not in the set already.
+
+        let inserted = !self.contains(card);
+        self.0 |= bit(card);
+        inserted
+    }
+
+    pub fn remove(&mut self, card: Card) -> bool {
+        //! Returns whether the card was in the set.
+
+        let removed = self.contains(card);
+        self.0 &= !bit(card);
+        removed
+    }
+
+    pub fn union(self, other: CardSet) -> CardSet {
+        CardSet(self.0 | other.0)
+    }
+
+    pub fn intersection(self, other: CardSet) -> CardSet {
+        CardSet(self.0 & other.0)
+    }
+
+    pub fn difference(self, other: CardSet) -> CardSet {
+        CardSet(self.0 & !other.0)
+    }
+
+    pub fn is_subset(self, other: CardSet) -> bool {
+        self.difference(other).is_empty()
+    }
+
+    pub fn iter(self) -> Iter {
+        Iter(self.0)
+    }
+
+    pub fn to_vec(self) -> Vec<Card> {
+        self.iter().collect()
+    }
+}
+
+fn index(card: Card) -> u32 {
+    u32::from(deck::deck_index(card))
+}
+
+fn bit(card: Card) -> u64 {
+    1 << index(card)
+}
+
+fn card_at(index: u32) -> Card {
+    Card {
+        suit: SUITS[index as usize / VALUES.len()],
+        value: VALUES[index as usize % VALUES.len()],
+    }
+}
+
+// Cards of a CardSet, in deck order
+pub struct Iter(u64);
+
+impl Iterator for Iter {
+    type Item = Card;
+
+    fn next(&mut self) -> Option<Card> {
+        if self.0 == 0 {
+            return None;
+        }
+        let index = self.0.trailing_zeros();
+        self.0 &= self.0 - 1;
+        Some(card_at(index))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.0.count_ones() as usize;
+        (len, Some(len))
+    }
+}
+
+impl IntoIterator for CardSet {
+    type Item = Card;
+    type IntoIter = Iter;
+
+    fn into_iter(self) -> Iter {
+        self.iter()
+    }
+}
+
+impl FromIterator<Card> for CardSet {
+    fn from_iter<I: IntoIterator<Item = Card>>(cards: I) -> Self {
+        let mut set = CardSet::empty();
+        for card in cards {
+            set.insert(card);
+        }
+        set
+    }
+}
+
+impl<'a> From<&'a [Card]> for CardSet {
+    fn from(cards: &'a [Card]) -> Self {
+        cards.iter().cloned().collect()
+    }
+}
+
+impl From<Vec<Card>> for CardSet {
+    fn from(cards: Vec<Card>) -> Self {
+        cards.into_iter().collect()
+    }
+}
+
+impl From<CardSet> for Vec<Card> {
+    fn from(set: CardSet) -> Self {
+        set.to_vec()
+    }
+}
+
+impl BitOr for CardSet {
+    type Output = CardSet;
+
+    fn bitor(self, other: CardSet) -> CardSet {
+        self.union(other)
+    }
+}
+
+impl BitAnd for CardSet {
+    type Output = CardSet;
+
+    fn bitand(self, other: CardSet) -> CardSet {
+        self.intersection(other)
+    }
+}
+
+impl Sub for CardSet {
+    type Output = CardSet;
+
+    fn sub(self, other: CardSet) -> CardSet {
+        self.difference(other)
+    }
+}
+
+impl Not for CardSet {
+    type Output = CardSet;
+
+    fn not(self) -> CardSet {
+        CardSet(!self.0 & ALL)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use deck::parse_cards;
+
+    #[test]
+    fn insert_and_remove() {
+        let mut set = CardSet::empty();
+        let card = "3C".parse().unwrap();
+        assert!(set.is_empty());
+        assert!(set.insert(card));
+        assert!(!set.insert(card));
+        assert!(set.contains(card));
+        assert_eq!(set.len(), 1);
+        assert!(set.remove(card));
+        assert!(!set.remove(card));
+        assert!(set.is_empty());
+    }
+
+    #[test]
+    fn full_in_deck_order() {
+        let cards = CardSet::full().to_vec();
+        assert_eq!(cards, deck::all_cards().collect::<Vec<_>>());
+        assert_eq!(CardSet::full().iter().size_hint(), (40, Some(40)));
+    }
+
+    #[test]
+    fn set_operations() {
+        let a = CardSet::from(parse_cards("1O 2O 3C").unwrap());
+        let b = CardSet::from(parse_cards("3C RE").unwrap());
+
+        assert_eq!((a | b).len(), 4);
+        assert_eq!((a & b).to_vec(), parse_cards("3C").unwrap());
+        assert_eq!((a - b).to_vec(), parse_cards("1O 2O").unwrap());
+        assert_eq!((!a).len(), 37);
+        assert!(!(!a).contains("1O".parse().unwrap()));
+        assert!((a & b).is_subset(a));
+        assert!(!a.is_subset(b));
+    }
+
+    #[test]
+    fn vec_conversions() {
+        // Duplicates collapse and cards come back in deck order
+        let cards = parse_cards("RE 1O RE 5B").unwrap();
+        let set = CardSet::from(&cards[..]);
+        assert_eq!(set.len(), 3);
+        let back: Vec<Card> = set.into();
+        assert_eq!(back, parse_cards("1O 5B RE").unwrap());
+    }
+}
@@ -1,6 +1,13 @@
-use rand::{self, Rng};
+use cardset::CardSet;
+use rand::prng::ChaChaRng;
+use rand::{self, Rng, SeedableRng};
+use std::fmt;
+use std::str::FromStr;
+use Error;
+use Result;
 
-#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub enum Suit {
     Bastos,
     Copas,
@@ -8,9 +15,36 @@ pub enum Suit {
     Espadas,
 }
 
-static SUITS: [Suit; 4] = [Suit::Oros, Suit::Copas, Suit::Bastos, Suit::Espadas];
+pub(crate) static SUITS: [Suit; 4] = [Suit::Oros, Suit::Copas, Suit::Bastos, Suit::Espadas];
 
-#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord)]
+impl fmt::Display for Suit {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let symbol = match *self {
+            Suit::Bastos => "B",
+            Suit::Copas => "C",
+            Suit::Oros => "O",
+            Suit::Espadas => "E",
+        };
+        f.write_str(symbol)
+    }
+}
+
+impl FromStr for Suit {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s {
+            "B" => Ok(Suit::Bastos),
+            "C" => Ok(Suit::Copas),
+            "O" => Ok(Suit::Oros),
+            "E" => Ok(Suit::Espadas),
+            _ => Err(Error::InvalidNotation),
+        }
+    }
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub enum Value {
     Uno,
     Dos,
@@ -41,7 +75,45 @@ impl Value {
     }
 }
 
-static VALUES: [Value; 10] = [
+impl fmt::Display for Value {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let symbol = match *self {
+            Value::Uno => "1",
+            Value::Dos => "2",
+            Value::Tres => "3",
+            Value::Cuatro => "4",
+            Value::Cinco => "5",
+            Value::Seis => "6",
+            Value::Siete => "7",
+            Value::Sota => "S",
+            Value::Caballo => "C",
+            Value::Rey => "R",
+        };
+        f.write_str(symbol)
+    }
+}
+
+impl FromStr for Value {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s {
+            "1" => Ok(Value::Uno),
+            "2" => Ok(Value::Dos),
+            "3" => Ok(Value::Tres),
+            "4" => Ok(Value::Cuatro),
+            "5" => Ok(Value::Cinco),
+            "6" => Ok(Value::Seis),
+            "7" => Ok(Value::Siete),
+            "S" => Ok(Value::Sota),
+            "C" => Ok(Value::Caballo),
+            "R" => Ok(Value::Rey),
+            _ => Err(Error::InvalidNotation),
+        }
+    }
+}
+
+pub(crate) static VALUES: [Value; 10] = [
     Value::Uno,
     Value::Dos,
     Value::Tres,
@@ -54,7 +126,8 @@ static VALUES: [Value; 10] = [
     Value::Rey,
 ];
 
-#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct Card {
     pub suit: Suit,
     pub value: Value,
@@ -82,39 +155,138 @@ impl Card {
     }
 }
 
+impl fmt::Display for Card {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}{}", self.value, self.suit)
+    }
+}
+
+impl FromStr for Card {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        //! Parse a card written as its value followed by its suit, e.g.: `3C` or `SE`.
+
+        if s.len() != 2 || !s.is_char_boundary(1) {
+            return Err(Error::InvalidNotation);
+        }
+        let (value, suit) = s.split_at(1);
+        Ok(Card {
+            suit: suit.parse()?,
+            value: value.parse()?,
+        })
+    }
+}
+
+pub fn parse_cards(s: &str) -> Result<Vec<Card>> {
+    //! Parse a whitespace separated list of cards, e.g.: `"3C 5C 6C"`.
+
+    s.split_whitespace().map(str::parse).collect()
+}
+
+const DECK_SIZE: usize = 40;
+
 #[derive(Clone, Eq, PartialEq)]
 pub struct Deck {
-    cards: Vec<Card>,
+    // Cards left in the deck
+    cards: CardSet,
+    // Draw order, cards are drawn from the end of order[..undrawn]
+    order: [Card; DECK_SIZE],
+    undrawn: usize,
 }
 
 impl Deck {
+    pub fn shuffled() -> Self {
+        Deck::shuffled_with(&mut rand::thread_rng())
+    }
+
+    pub fn shuffled_with<R: Rng + ?Sized>(rng: &mut R) -> Self {
+        let mut deck = Deck::default();
+        deck.shuffle_with(rng);
+        deck
+    }
+
+    pub fn from_seed(seed: u64) -> Self {
+        //! Shuffle deterministically, so the same seed always yields the same deck. ChaCha is used
+        //! rather than StdRng, whose algorithm may change between rand versions.
+
+        Deck::shuffled_with(&mut ChaChaRng::seed_from_u64(seed))
+    }
+
     pub fn draw(&mut self) -> Option<Card> {
-        self.cards.pop()
+        while self.undrawn > 0 {
+            self.undrawn -= 1;
+            let card = self.order[self.undrawn];
+            if self.cards.remove(card) {
+                return Some(card);
+            }
+        }
+        None
     }
 
-    fn shuffle(&mut self) {
-        let mut rng = rand::thread_rng();
-        rng.shuffle(&mut self.cards);
+    pub fn shuffle(&mut self) {
+        self.shuffle_with(&mut rand::thread_rng());
+    }
+
+    pub fn shuffle_with<R: Rng + ?Sized>(&mut self, rng: &mut R) {
+        rng.shuffle(&mut self.order[..self.undrawn]);
     }
 
     pub fn remaining_cards(&self) -> usize {
         self.cards.len()
     }
+
+    pub fn card_set(&self) -> CardSet {
+        self.cards
+    }
+
+    #[cfg(test)]
+    pub(crate) fn remove(&mut self, card: Card) -> bool {
+        //! Take a specific card out of the deck, to set up rounds in tests.
+
+        self.cards.remove(card)
+    }
+}
+
+pub(crate) fn deck_index(card: Card) -> u8 {
+    //! Position of the card in deck order, from 0 to 39.
+
+    let suit = match card.suit {
+        Suit::Oros => 0,
+        Suit::Copas => 1,
+        Suit::Bastos => 2,
+        Suit::Espadas => 3,
+    };
+    suit * 10 + card.value as u8
+}
+
+pub fn all_cards() -> impl Iterator<Item = Card> {
+    //! Every card in the deck, in deck order.
+
+    iproduct!(SUITS.iter(), VALUES.iter()).map(|(&suit, &value)| Card { suit, value })
 }
 
 impl Default for Deck {
     fn default() -> Self {
-        let mut cards = vec![];
-        for (&suit, &value) in iproduct!(SUITS.into_iter(), VALUES.into_iter()) {
-            cards.push(Card { suit, value })
+        let mut order = [Card {
+            suit: Suit::Oros,
+            value: Value::Uno,
+        }; DECK_SIZE];
+        for (slot, card) in order.iter_mut().zip(all_cards()) {
+            *slot = card;
+        }
+        Self {
+            cards: CardSet::full(),
+            order,
+            undrawn: DECK_SIZE,
         }
-        Self { cards }
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use rand::StdRng;
 
     #[test]
     fn card_is_perico() {
@@ -206,8 +378,92 @@ mod tests {
     fn shuffle() {
         let deck: Deck = Default::default();
         let mut deck2 = deck.clone();
-        deck2.shuffle();
+        deck2.shuffle_with(&mut StdRng::seed_from_u64(42));
         assert!(deck != deck2);
+        assert_eq!(deck2.remaining_cards(), 40);
+    }
+
+    #[test]
+    fn from_seed() {
+        assert!(Deck::from_seed(7) == Deck::from_seed(7));
+        assert!(Deck::from_seed(7) != Deck::from_seed(8));
+        assert!(Deck::from_seed(7) == Deck::shuffled_with(&mut ChaChaRng::seed_from_u64(7)));
+
+        // Pinned, so a seed keeps dealing the same game
+        let mut deck = Deck::from_seed(7);
+        let drawn = (0..5).map(|_| deck.draw().unwrap()).collect::<Vec<_>>();
+        assert_eq!(drawn, parse_cards("2B SE SO 6C 6B").unwrap());
+    }
+
+    #[test]
+    fn card_notation() {
+        let card: Card = "3C".parse().unwrap();
+        assert_eq!(
+            card,
+            Card {
+                suit: Suit::Copas,
+                value: Value::Tres,
+            }
+        );
+        let card: Card = "RB".parse().unwrap();
+        assert_eq!(
+            card,
+            Card {
+                suit: Suit::Bastos,
+                value: Value::Rey,
+            }
+        );
+        assert_eq!(card.to_string(), "RB");
+
+        assert_eq!("3".parse::<Card>(), Err(Error::InvalidNotation));
+        assert_eq!("3CC".parse::<Card>(), Err(Error::InvalidNotation));
+        assert_eq!("8C".parse::<Card>(), Err(Error::InvalidNotation));
+        assert_eq!("3X".parse::<Card>(), Err(Error::InvalidNotation));
+        assert_eq!("éC".parse::<Card>(), Err(Error::InvalidNotation));
+    }
+
+    #[test]
+    fn card_notation_round_trip() {
+        let mut deck = Deck::default();
+        while let Some(card) = deck.draw() {
+            assert_eq!(card.to_string().parse(), Ok(card));
+        }
+    }
+
+    #[test]
+    #[cfg(feature = "serde")]
+    fn card_serde() {
+        use serde_json;
+
+        let card = Card {
+            suit: Suit::Copas,
+            value: Value::Tres,
+        };
+        let json = serde_json::to_string(&card).unwrap();
+        assert_eq!(json, r#"{"suit":"Copas","value":"Tres"}"#);
+        assert_eq!(serde_json::from_str::<Card>(&json).unwrap(), card);
+    }
+
+    #[test]
+    fn parse_hand() {
+        let cards = parse_cards("7O  SE\tCB").unwrap();
+        let expected = vec![
+            Card {
+                suit: Suit::Oros,
+                value: Value::Siete,
+            },
+            Card {
+                suit: Suit::Espadas,
+                value: Value::Sota,
+            },
+            Card {
+                suit: Suit::Bastos,
+                value: Value::Caballo,
+            },
+        ];
+        assert_eq!(cards, expected);
+        assert_eq!(parse_cards(""), Ok(vec![]));
+        assert_eq!(parse_cards("3C 9C"), Err(Error::InvalidNotation));
     }
 
     #[test]
@@ -0,0 +1,64 @@
+use deck::Card;
+use std::error;
+use std::fmt;
+use std::result;
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum Error {
+    // The card is not in the seat's hand
+    CardNotInHand(Card),
+    // The seat tried to play a card when it wasn't its turn
+    OutOfTurn(usize),
+    DealerNotSeated,
+    // The deck ran out of cards while setting up the round
+    NotEnoughCards,
+    IllegalBet,
+    // Cards can't be played while a bet is waiting for an answer
+    BetPending,
+    RoundNotFinished,
+    GameFinished,
+    // The finished round was dealt from another game, or another round was annotated since
+    ForeignRound,
+    CorruptScoreboard,
+    // Points don't fit in a score
+    ScoreOverflow,
+    // No round was annotated at that position on the scoreboard
+    RoundNotFound,
+    // The card shows up more than once among the deck, the marker and the seats
+    DuplicateCard(Card),
+    // The card is nowhere in the round
+    MissingCard(Card),
+    // Seats were dealt different numbers of cards
+    UnevenHands,
+    // The text is not a valid card, suit or value
+    InvalidNotation,
+}
+
+pub type Result<T> = result::Result<T, Error>;
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            Error::CardNotInHand(card) => write!(f, "card {:?} is not in hand", card),
+            Error::OutOfTurn(seat) => write!(f, "seat {} played out of turn", seat),
+            Error::DealerNotSeated => write!(f, "the dealer is not seated"),
+            Error::NotEnoughCards => write!(f, "not enough cards left in the deck"),
+            Error::IllegalBet => write!(f, "illegal bet"),
+            Error::BetPending => write!(f, "a bet is waiting for an answer"),
+            Error::RoundNotFinished => write!(f, "the round is not finished"),
+            Error::GameFinished => write!(f, "the game is already finished"),
+            Error::ForeignRound => write!(f, "the round doesn't belong on this scoreboard"),
+            Error::CorruptScoreboard => write!(f, "the scoreboard is corrupt"),
+            Error::ScoreOverflow => write!(f, "the score is too large"),
+            Error::RoundNotFound => write!(f, "no such round on the scoreboard"),
+            Error::DuplicateCard(card) => {
+                write!(f, "card {:?} is in the round more than once", card)
+            }
+            Error::MissingCard(card) => write!(f, "card {:?} is missing from the round", card),
+            Error::UnevenHands => write!(f, "seats hold different numbers of cards"),
+            Error::InvalidNotation => write!(f, "invalid card notation"),
+        }
+    }
+}
+
+impl error::Error for Error {}
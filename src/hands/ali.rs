@@ -1,31 +1,113 @@
-use deck::{Card, Value};
-use hands::Hand;
+use deck::{Card, Value, VALUES};
+use hands::{describe_cards, split_wildcards, Hand, Wildcard};
 use itertools::Itertools;
 use std::cmp::Ordering;
 
-#[derive(Debug, Eq, PartialEq)]
+#[derive(Debug, Clone, Eq, PartialEq)]
 pub struct Ali {
     cards: Vec<Card>,
+    wildcards: Vec<Wildcard>,
 }
 
-impl<'a> Hand<'a> for Ali {
-    fn from_cards(cards: &[Card], _marker: Card) -> Option<Self> {
-        Ali::from_cards_slice(cards)
+impl Hand for Ali {
+    const NAME: &'static str = "ali";
+
+    fn from_cards(cards: &[Card], marker: Card) -> Option<Self> {
+        let (naturals, wildcards) = split_wildcards(cards, marker);
+        Ali::from_naturals_and_wildcards(&naturals, &wildcards)
+    }
+
+    fn score(&self) -> u8 {
+        match (self.is_ali_3_cards(), self.is_ali_aces()) {
+            (true, true) => 6,
+            (true, false) | (false, true) => 3,
+            (false, false) => 1,
+        }
+    }
+
+    fn describe(&self) -> String {
+        describe_cards(Self::NAME, &self.cards, &self.wildcards)
     }
 }
 
 impl Ali {
+    #[cfg(test)]
     fn from_cards_slice(cards: &[Card]) -> Option<Self> {
-        cards
-            .into_iter()
-            .sorted_by_key(|card| card.value)
+        Self::from_naturals_and_wildcards(cards, &[])
+    }
+
+    fn from_naturals_and_wildcards(naturals: &[Card], wildcards: &[Card]) -> Option<Self> {
+        //! Try every value for each wildcard and keep the best ali.
+
+        let assignments = if wildcards.is_empty() {
+            vec![vec![]]
+        } else {
+            wildcards
+                .iter()
+                .map(|_| VALUES.iter().cloned())
+                .multi_cartesian_product()
+                .collect()
+        };
+
+        let mut best: Option<Ali> = None;
+        for values in assignments {
+            let candidate = Self::with_wildcard_values(naturals, wildcards, &values);
+            if candidate > best {
+                best = candidate;
+            }
+        }
+        best
+    }
+
+    fn with_wildcard_values(
+        naturals: &[Card],
+        wildcards: &[Card],
+        values: &[Value],
+    ) -> Option<Self> {
+        let wildcards = wildcards
+            .iter()
+            .zip(values)
+            .map(|(&card, &value)| Wildcard { card, value })
+            .collect::<Vec<_>>();
+        let group = naturals
+            .iter()
+            .map(|&card| (card, card.value))
+            .chain(
+                wildcards
+                    .iter()
+                    .map(|wildcard| (wildcard.card, wildcard.value)),
+            )
+            .sorted_by_key(|&(_, value)| value)
             .into_iter()
-            .group_by(|card| card.value)
+            .group_by(|&(_, value)| value)
             .into_iter()
-            .map(|(_, group)| group.cloned().collect::<Vec<_>>())
+            .map(|(_, group)| group.map(|(card, _)| card).collect::<Vec<_>>())
             .filter(|group| group.len() >= 2)
-            .max_by_key(|group| group.len())
-            .map(|group| Self { cards: group })
+            .max_by_key(|group| group.len())?;
+        let wildcards = wildcards
+            .into_iter()
+            .filter(|wildcard| group.contains(&wildcard.card))
+            .collect();
+        Some(Self {
+            cards: group,
+            wildcards,
+        })
+    }
+
+    pub fn cards(&self) -> &[Card] {
+        &self.cards
+    }
+
+    pub fn wildcards(&self) -> &[Wildcard] {
+        //! Wildcards used in the ali, along with the value each one took.
+
+        &self.wildcards
+    }
+
+    pub fn value(&self) -> Value {
+        self.wildcards
+            .first()
+            .map_or(self.cards[0].value, |wildcard| wildcard.value)
     }
 
     fn is_ali_3_cards(&self) -> bool {
@@ -33,15 +115,7 @@ impl Ali {
     }
 
     fn is_ali_aces(&self) -> bool {
-        self.cards.iter().all(|card| card.value == Value::Uno)
-    }
-
-    pub fn score(&self) -> u8 {
-        match (self.is_ali_3_cards(), self.is_ali_aces()) {
-            (true, true) => 6,
-            (true, false) | (false, true) => 3,
-            (false, false) => 1,
-        }
+        self.value() == Value::Uno
     }
 }
 
@@ -52,8 +126,8 @@ impl Ord for Ali {
         } else if !self.is_ali_3_cards() && other.is_ali_3_cards() {
             Ordering::Less
         } else {
-            let self_value = self.cards[0].value;
-            let other_value = other.cards[0].value;
+            let self_value = self.value();
+            let other_value = other.value();
             // There is a special case for Uno, being the highest value in Ali
             if self_value == Value::Uno && other_value != Value::Uno {
                 Ordering::Greater
@@ -75,7 +149,8 @@ impl PartialOrd for Ali {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use deck::{Suit, Value};
+    use cardset::CardSet;
+    use deck::{parse_cards, Suit, Value};
 
     #[test]
     fn is_ali_3_true() {
@@ -433,4 +508,88 @@ mod tests {
         result.sort();
         assert_eq!(expected, result);
     }
+
+    #[test]
+    fn ali_wildcard_completes_pair() {
+        // Caballo de oros is the perico
+        let marker = "1O".parse().unwrap();
+        let hand = parse_cards("4C 4B CO").unwrap();
+        let ali = Ali::from_cards(&hand, marker).unwrap();
+
+        assert!(ali.is_ali_3_cards());
+        assert_eq!(ali.score(), 3);
+        assert_eq!(
+            ali.wildcards(),
+            &[Wildcard {
+                card: "CO".parse().unwrap(),
+                value: Value::Cuatro,
+            }]
+        );
+        let natural = Ali::from_cards_slice(&parse_cards("4C 4B 4E").unwrap()).unwrap();
+        assert_eq!(ali.cmp(&natural), Ordering::Equal);
+    }
+
+    #[test]
+    fn ali_wildcard_aces() {
+        // Rey de copas is the perica when the sota is the marker
+        let marker = "SC".parse().unwrap();
+        let hand = parse_cards("1B RC 1E").unwrap();
+        let ali = Ali::from_cards(&hand, marker).unwrap();
+
+        assert!(ali.is_ali_aces());
+        assert_eq!(ali.score(), 6);
+
+        // A single ace pairs with the wildcard before any other card
+        let hand = parse_cards("1B RC 7E").unwrap();
+        let ali = Ali::from_cards(&hand, marker).unwrap();
+        assert!(ali.is_ali_aces());
+        assert!(!ali.is_ali_3_cards());
+        assert_eq!(ali.score(), 3);
+    }
+
+    #[test]
+    fn ali_from_card_set() {
+        let marker = "1O".parse().unwrap();
+        let cards = parse_cards("4C 4B CO").unwrap();
+        assert_eq!(
+            Ali::from_card_set(CardSet::from(&cards[..]), marker),
+            Ali::from_cards(&cards, marker)
+        );
+    }
+
+    #[test]
+    fn ali_two_wildcards() {
+        let marker = "1O".parse().unwrap();
+        let hand = parse_cards("SO 6E CO").unwrap();
+        let ali = Ali::from_cards(&hand, marker).unwrap();
+
+        assert!(ali.is_ali_3_cards());
+        assert_eq!(ali.value(), Value::Seis);
+        assert_eq!(ali.wildcards().len(), 2);
+    }
+
+    #[test]
+    fn ali_without_wildcards_ignores_marker() {
+        let marker = "1O".parse().unwrap();
+        let hand = parse_cards("1C 3B 5E").unwrap();
+        assert!(Ali::from_cards(&hand, marker).is_none());
+
+        let hand = parse_cards("SC SB 5E").unwrap();
+        let ali = Ali::from_cards(&hand, marker).unwrap();
+        assert!(ali.wildcards().is_empty());
+        assert_eq!(ali, Ali::from_cards_slice(&hand).unwrap());
+    }
+
+    #[test]
+    fn ali_wildcard_ordering() {
+        let marker = "1O".parse().unwrap();
+        let wild_reyes = Ali::from_cards(&parse_cards("RC RB SO").unwrap(), marker);
+        let reyes = Ali::from_cards(&parse_cards("RC RB RE").unwrap(), marker);
+        let wild_pair = Ali::from_cards(&parse_cards("3C 7B SO").unwrap(), marker);
+        let pair = Ali::from_cards(&parse_cards("6C 6B 7E").unwrap(), marker);
+
+        assert_eq!(wild_reyes.cmp(&reyes), Ordering::Equal);
+        assert!(wild_pair > pair);
+        assert!(wild_reyes > wild_pair);
+    }
 }
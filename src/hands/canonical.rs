@@ -0,0 +1,169 @@
+use deck::{self, Card, Suit, SUITS};
+use itertools::Itertools;
+use scorers::truc::truc_rank;
+use std::collections::HashMap;
+use std::iter;
+
+// A (hand, marker) with its suits relabeled, standing for every deal that only differs in suit names
+#[derive(Debug, Clone, Eq, PartialEq, Hash)]
+pub struct CanonicalHand {
+    // In deck order
+    pub cards: Vec<Card>,
+    pub marker: Card,
+}
+
+impl CanonicalHand {
+    fn key(&self) -> (Vec<u8>, u8) {
+        let cards = self
+            .cards
+            .iter()
+            .map(|&card| deck::deck_index(card))
+            .collect();
+        (cards, deck::deck_index(self.marker))
+    }
+}
+
+pub fn canonicalize(cards: &[Card], marker: Card) -> CanonicalHand {
+    //! Relabel suits so that equivalent deals share the same form.
+    //! A relabeling is only allowed if every card, the marker included, keeps its truc rank, which keeps
+    //! the aces of espadas and bastos, the sevens of oros and espadas, and the perico and perica in place.
+    //! Flor, secansa and ali only depend on values and on which cards share a suit, so they are kept too.
+
+    canonicalize_with(&suit_permutations(), cards, marker)
+}
+
+fn canonicalize_with(permutations: &[Vec<Suit>], cards: &[Card], marker: Card) -> CanonicalHand {
+    permutations
+        .iter()
+        .filter_map(|permutation| {
+            let relabel = |card: Card| Card {
+                suit: permutation[suit_position(card.suit)],
+                value: card.value,
+            };
+            let relabeled_marker = relabel(marker);
+            let keeps_ranks = iter::once(&marker)
+                .chain(cards)
+                .all(|&card| truc_rank(relabel(card), relabeled_marker) == truc_rank(card, marker));
+            if !keeps_ranks {
+                return None;
+            }
+            let mut relabeled = cards.iter().map(|&card| relabel(card)).collect::<Vec<_>>();
+            relabeled.sort_by_key(|&card| deck::deck_index(card));
+            Some(CanonicalHand {
+                cards: relabeled,
+                marker: relabeled_marker,
+            })
+        })
+        .min_by_key(|hand| hand.key())
+        .expect("leaving suits as they are is always allowed")
+}
+
+pub fn canonical_classes(hand_size: usize) -> Vec<(CanonicalHand, usize)> {
+    //! Every canonical class of hand_size cards plus a marker, with the number of raw deals in it.
+    //! Sorted in deck order of the canonical form.
+
+    let mut classes: HashMap<CanonicalHand, usize> = HashMap::new();
+    let cards = deck::all_cards().collect::<Vec<_>>();
+    let permutations = suit_permutations();
+    for &marker in &cards {
+        for hand in cards
+            .iter()
+            .cloned()
+            .filter(|&card| card != marker)
+            .combinations(hand_size)
+        {
+            *classes
+                .entry(canonicalize_with(&permutations, &hand, marker))
+                .or_insert(0) += 1;
+        }
+    }
+    let mut classes = classes.into_iter().collect::<Vec<_>>();
+    classes.sort_by_key(|(hand, _)| hand.key());
+    classes
+}
+
+fn suit_position(suit: Suit) -> usize {
+    SUITS.iter().position(|&s| s == suit).unwrap()
+}
+
+fn suit_permutations() -> Vec<Vec<Suit>> {
+    //! All 24 orderings of the suits, each one mapping SUITS[i] to permutation[i].
+
+    let mut permutations = vec![vec![]];
+    for _ in 0..SUITS.len() {
+        permutations = permutations
+            .into_iter()
+            .flat_map(|permutation: Vec<Suit>| {
+                SUITS
+                    .iter()
+                    .filter(|suit| !permutation.contains(suit))
+                    .map(|&suit| {
+                        let mut next = permutation.clone();
+                        next.push(suit);
+                        next
+                    })
+                    .collect::<Vec<_>>()
+            })
+            .collect();
+    }
+    permutations
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use deck::parse_cards;
+
+    fn canonical(cards: &str, marker: &str) -> CanonicalHand {
+        canonicalize(&parse_cards(cards).unwrap(), marker.parse().unwrap())
+    }
+
+    #[test]
+    fn canonicalize_plain_cards() {
+        // Nothing special about these cards, so only the flor matters
+        assert_eq!(canonical("4C 5C 6C", "RB"), canonical("4B 5B 6B", "RE"));
+        assert_eq!(canonical("4C 5O 6C", "RB"), canonical("4E 5C 6E", "RO"));
+        assert_ne!(canonical("4C 5C 6C", "RB"), canonical("4C 5O 6C", "RB"));
+        // Same suit as the marker or not
+        assert_ne!(canonical("4C 5O 6E", "RB"), canonical("4C 5O 6E", "RC"));
+    }
+
+    #[test]
+    fn canonicalize_special_cards() {
+        assert_ne!(canonical("1E 5C 6C", "RB"), canonical("1C 5E 6E", "RB"));
+        assert_ne!(canonical("1B 5C 6C", "RO"), canonical("1E 5C 6C", "RO"));
+        assert_ne!(canonical("7O 5C 6B", "RE"), canonical("7C 5O 6B", "RE"));
+        // Bobo aces and sevens are interchangeable
+        assert_eq!(canonical("1O 7B 4E", "RC"), canonical("1C 7B 4E", "RO"));
+        // Perico of a different suit
+        assert_eq!(canonical("CO 4B 5B", "1O"), canonical("CC 4B 5B", "1C"));
+        // The marker keeps its truc rank too
+        assert_ne!(canonical("4C 5C 6C", "1E"), canonical("4C 5C 6C", "1O"));
+    }
+
+    #[test]
+    fn canonicalize_is_stable() {
+        let hand = canonical("SE 7O 1B", "3C");
+        assert_eq!(canonicalize(&hand.cards, hand.marker), hand);
+        assert_eq!(canonical("7O 1B SE", "3C"), hand);
+    }
+
+    #[test]
+    fn classes_cover_every_deal() {
+        let classes = canonical_classes(1);
+        let deals: usize = classes.iter().map(|&(_, count)| count).sum();
+        assert_eq!(deals, 40 * 39);
+        assert_eq!(classes.len(), 350);
+        for (hand, _) in &classes {
+            assert_eq!(&canonicalize(&hand.cards, hand.marker), hand);
+        }
+    }
+
+    #[test]
+    fn classes_cover_every_2_card_deal() {
+        let classes = canonical_classes(2);
+        let deals: usize = classes.iter().map(|&(_, count)| count).sum();
+        assert_eq!(deals, 40 * 741);
+        assert_eq!(classes.len(), 5292);
+    }
+}
@@ -0,0 +1,119 @@
+use hands::Hand;
+use std::cmp::Ordering;
+use Team;
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct RankedHand<H> {
+    pub seat: usize,
+    pub team: Team,
+    pub hand: H,
+}
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct HandComparison<H> {
+    // Best hand first. Equal hands keep the order from the hand, as the first one wins.
+    ranking: Vec<RankedHand<H>>,
+}
+
+impl<H: Hand> HandComparison<H> {
+    pub fn new(mut hands_from_hand: Vec<RankedHand<H>>) -> Self {
+        //! Hands must be listed starting from the hand (the seat after the dealer), in table order.
+
+        // sort_by is stable, so ties stay in table order
+        hands_from_hand.sort_by(|a, b| b.hand.cmp(&a.hand));
+        HandComparison {
+            ranking: hands_from_hand,
+        }
+    }
+
+    pub fn ranking(&self) -> &[RankedHand<H>] {
+        &self.ranking
+    }
+
+    pub fn deciding_hand(&self) -> Option<&RankedHand<H>> {
+        self.ranking.first()
+    }
+
+    pub fn deciding_seat(&self) -> Option<usize> {
+        self.deciding_hand().map(|ranked| ranked.seat)
+    }
+
+    pub fn winner(&self) -> Option<Team> {
+        self.deciding_hand().map(|ranked| ranked.team)
+    }
+
+    pub fn tie_break_used(&self) -> bool {
+        //! Whether the other team held an equally good hand, and lost it for being further from the hand.
+
+        match self.deciding_hand() {
+            Some(best) => self
+                .ranking
+                .iter()
+                .take_while(|ranked| ranked.hand.cmp(&best.hand) == Ordering::Equal)
+                .any(|ranked| ranked.team != best.team),
+            None => false,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use deck::parse_cards;
+    use hands::ali::Ali;
+
+    fn ranked(seat: usize, cards: &str) -> RankedHand<Ali> {
+        let marker = "4O".parse().unwrap();
+        RankedHand {
+            seat,
+            team: match seat % 2 {
+                0 => Team::Team1,
+                _ => Team::Team2,
+            },
+            hand: Ali::from_cards(&parse_cards(cards).unwrap(), marker).unwrap(),
+        }
+    }
+
+    #[test]
+    fn comparison_empty() {
+        let comparison = HandComparison::<Ali>::new(vec![]);
+        assert_eq!(comparison.winner(), None);
+        assert_eq!(comparison.deciding_seat(), None);
+        assert!(!comparison.tie_break_used());
+    }
+
+    #[test]
+    fn comparison_ranking() {
+        let comparison = HandComparison::new(vec![
+            ranked(2, "5C 5B 1E"),
+            ranked(3, "RC RB RE"),
+            ranked(0, "1C 1B 3E"),
+        ]);
+        let seats = comparison
+            .ranking()
+            .iter()
+            .map(|ranked| ranked.seat)
+            .collect::<Vec<_>>();
+        assert_eq!(seats, vec![3, 0, 2]);
+        assert_eq!(comparison.deciding_seat(), Some(3));
+        assert_eq!(comparison.winner(), Some(Team::Team2));
+        assert!(!comparison.tie_break_used());
+    }
+
+    #[test]
+    fn comparison_tie_break() {
+        let comparison = HandComparison::new(vec![ranked(1, "3C 3B 1E"), ranked(2, "3O 3E 5E")]);
+        assert_eq!(comparison.deciding_seat(), Some(1));
+        assert_eq!(comparison.winner(), Some(Team::Team2));
+        assert!(comparison.tie_break_used());
+
+        // A tie between partners doesn't change which team wins
+        let comparison = HandComparison::new(vec![
+            ranked(1, "3C 3B 1E"),
+            ranked(3, "3O 3E 5E"),
+            ranked(2, "2O 2E 5E"),
+        ]);
+        assert_eq!(comparison.deciding_seat(), Some(1));
+        assert!(!comparison.tie_break_used());
+    }
+}
@@ -3,23 +3,37 @@ use hands::Hand;
 use itertools::Itertools;
 use std::cmp::Ordering;
 
-#[derive(Debug, Eq, PartialEq, Copy, Clone)]
-pub struct Flor<'a> {
-    cards: &'a [Card],
+#[derive(Debug, Eq, PartialEq, Clone)]
+pub struct Flor {
+    cards: Vec<Card>,
     marker: Card,
 }
 
-impl<'a> Hand<'a> for Flor<'a> {
-    fn from_cards(cards: &'a [Card], marker: Card) -> Option<Self> {
+impl Hand for Flor {
+    const NAME: &'static str = "flor";
+
+    fn from_cards(cards: &[Card], marker: Card) -> Option<Self> {
         if Self::is_flor(cards, marker) {
-            Some(Flor { cards, marker })
+            Some(Flor {
+                cards: cards.to_vec(),
+                marker,
+            })
         } else {
             None
         }
     }
+
+    fn score(&self) -> u8 {
+        3
+    }
+
+    fn describe(&self) -> String {
+        let cards = self.cards.iter().map(|card| card.to_string()).join(" ");
+        format!("{} {} {}", Self::NAME, self.value(), cards)
+    }
 }
 
-impl<'a> Flor<'a> {
+impl Flor {
     fn is_flor(cards: &[Card], marker: Card) -> bool {
         if cards.len() != 3 {
             return false;
@@ -32,7 +46,7 @@ impl<'a> Flor<'a> {
             .all_equal()
     }
 
-    fn value(&self) -> u8 {
+    pub fn value(&self) -> u8 {
         let total: u8 = self.cards
             .iter()
             .map(|card| match card.value {
@@ -54,13 +68,13 @@ impl<'a> Flor<'a> {
     }
 }
 
-impl<'a> Ord for Flor<'a> {
+impl Ord for Flor {
     fn cmp(&self, other: &Self) -> Ordering {
         self.value().cmp(&other.value())
     }
 }
 
-impl<'a> PartialOrd for Flor<'a> {
+impl PartialOrd for Flor {
     fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
         Some(self.cmp(other))
     }
@@ -1,9 +1,71 @@
 pub mod ali;
+mod canonical;
+mod comparison;
 pub mod flor;
+mod registry;
+mod report;
 pub mod secansa;
+mod tables;
 
-use deck::Card;
+pub use self::canonical::{canonical_classes, canonicalize, CanonicalHand};
+pub use self::comparison::{HandComparison, RankedHand};
+pub use self::registry::{Joc, JocKind, JocRegistry};
+pub use self::report::{analyze, HandReport};
+pub use self::tables::EvalTables;
 
-pub trait Hand<'a>: Ord + Sized {
-    fn from_cards(cards: &'a [Card], marker: Card) -> Option<Self>;
+use cardset::CardSet;
+use deck::{Card, Value};
+
+pub trait Hand: Ord + Sized {
+    // Name of the joc, e.g.: "secansa"
+    const NAME: &'static str;
+
+    fn from_cards(cards: &[Card], marker: Card) -> Option<Self>;
+
+    fn from_card_set(cards: CardSet, marker: Card) -> Option<Self> {
+        // A set never holds more than the 40 cards of the deck, so they fit on the stack
+        let mut buffer = [marker; 40];
+        for (slot, card) in buffer.iter_mut().zip(cards.iter()) {
+            *slot = card;
+        }
+        Self::from_cards(&buffer[..cards.len()], marker)
+    }
+
+    // Points the hand is worth to its team when the joc is won
+    fn score(&self) -> u8;
+
+    fn describe(&self) -> String;
+}
+
+// A perico or perica standing in for another card
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub struct Wildcard {
+    pub card: Card,
+    // Value the card took in the hand
+    pub value: Value,
+}
+
+fn is_wildcard(card: Card, marker: Card) -> bool {
+    card.is_perico(marker) || card.is_perica(marker)
+}
+
+fn describe_cards(name: &str, cards: &[Card], wildcards: &[Wildcard]) -> String {
+    //! e.g.: "secansa 4C CO(5) 6B", where the perico stands in for a 5.
+
+    let cards = cards
+        .iter()
+        .map(
+            |card| match wildcards.iter().find(|wildcard| wildcard.card == *card) {
+                Some(wildcard) => format!("{}({})", card, wildcard.value),
+                None => card.to_string(),
+            },
+        )
+        .collect::<Vec<_>>();
+    format!("{} {}", name, cards.join(" "))
+}
+
+fn split_wildcards(cards: &[Card], marker: Card) -> (Vec<Card>, Vec<Card>) {
+    //! Split cards into natural cards and wildcards.
+
+    cards.iter().partition(|&&card| !is_wildcard(card, marker))
 }
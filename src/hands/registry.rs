@@ -0,0 +1,236 @@
+use deck::Card;
+use hands::{ali, flor, secansa, Hand};
+use scoreboard::Points;
+use std::marker::PhantomData;
+use Result;
+use Round;
+use Team;
+
+pub trait Joc {
+    fn name(&self) -> &'static str;
+
+    fn score(&self, cards: &[Card], marker: Card) -> Option<u8>;
+
+    fn describe(&self, cards: &[Card], marker: Card) -> Option<String>;
+
+    // Team holding the best hand of this joc, if anyone holds it
+    fn winner(&self, round: &Round) -> Option<Team>;
+
+    // Points of every hand of this joc held by the team
+    fn team_score(&self, round: &Round, team: Team) -> Result<Points>;
+}
+
+// Any Hand can be registered as a Joc
+pub struct JocKind<H>(PhantomData<H>);
+
+impl<H> Default for JocKind<H> {
+    fn default() -> Self {
+        JocKind(PhantomData)
+    }
+}
+
+impl<H: Hand> Joc for JocKind<H> {
+    fn name(&self) -> &'static str {
+        H::NAME
+    }
+
+    fn score(&self, cards: &[Card], marker: Card) -> Option<u8> {
+        H::from_cards(cards, marker).map(|hand| hand.score())
+    }
+
+    fn describe(&self, cards: &[Card], marker: Card) -> Option<String> {
+        H::from_cards(cards, marker).map(|hand| hand.describe())
+    }
+
+    fn winner(&self, round: &Round) -> Option<Team> {
+        round.get_winner_from_cards::<H>()
+    }
+
+    fn team_score(&self, round: &Round, team: Team) -> Result<Points> {
+        round.team_hand_score::<H>(team)
+    }
+}
+
+// Jocs by name. The scorers look up flor, secansa and ali here, so house rules can replace them
+pub struct JocRegistry {
+    jocs: Vec<Box<dyn Joc>>,
+}
+
+impl Default for JocRegistry {
+    fn default() -> Self {
+        let mut registry = JocRegistry::empty();
+        registry.register::<flor::Flor>();
+        registry.register::<secansa::Secansa>();
+        registry.register::<ali::Ali>();
+        registry
+    }
+}
+
+impl JocRegistry {
+    pub fn empty() -> Self {
+        JocRegistry { jocs: vec![] }
+    }
+
+    pub fn register<H: Hand + 'static>(&mut self) {
+        self.register_joc(Box::new(JocKind::<H>::default()));
+    }
+
+    pub fn register_joc(&mut self, joc: Box<dyn Joc>) {
+        //! A joc registered under an existing name replaces it.
+
+        match self.jocs.iter().position(|j| j.name() == joc.name()) {
+            Some(pos) => self.jocs[pos] = joc,
+            None => self.jocs.push(joc),
+        }
+    }
+
+    pub fn get(&self, name: &str) -> Option<&dyn Joc> {
+        self.iter().find(|joc| joc.name() == name)
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &dyn Joc> {
+        self.jocs.iter().map(|joc| joc.as_ref())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use deck::{self, parse_cards};
+    use scoreboard::RoundScoreSection;
+    use scorers;
+    use Game;
+    use Player;
+
+    // House rule: three cards of the same colour, oros and copas being red
+    #[derive(Debug, Eq, PartialEq, Ord, PartialOrd)]
+    struct Colors(bool);
+
+    impl Hand for Colors {
+        const NAME: &'static str = "colors";
+
+        fn from_cards(cards: &[Card], _marker: Card) -> Option<Self> {
+            use deck::Suit::*;
+
+            let red = cards.iter().filter(|card| match card.suit {
+                Oros | Copas => true,
+                Bastos | Espadas => false,
+            });
+            match red.count() {
+                0 => Some(Colors(false)),
+                3 => Some(Colors(true)),
+                _ => None,
+            }
+        }
+
+        fn score(&self) -> u8 {
+            2
+        }
+
+        fn describe(&self) -> String {
+            if self.0 { "red" } else { "black" }.to_string()
+        }
+    }
+
+    // House rule: only red hands make an ali
+    #[derive(Debug, Eq, PartialEq, Ord, PartialOrd)]
+    struct RedAli;
+
+    impl Hand for RedAli {
+        const NAME: &'static str = "ali";
+
+        fn from_cards(cards: &[Card], marker: Card) -> Option<Self> {
+            match Colors::from_cards(cards, marker) {
+                Some(Colors(true)) => Some(RedAli),
+                _ => None,
+            }
+        }
+
+        fn score(&self) -> u8 {
+            1
+        }
+
+        fn describe(&self) -> String {
+            "red ali".to_string()
+        }
+    }
+
+    #[test]
+    fn default_jocs() {
+        let registry = JocRegistry::default();
+        let names = registry.iter().map(|joc| joc.name()).collect::<Vec<_>>();
+        assert_eq!(names, vec!["flor", "secansa", "ali"]);
+
+        let marker = "4B".parse().unwrap();
+        let cards = parse_cards("5C 6C 7C").unwrap();
+        let secansa = registry.get("secansa").unwrap();
+        assert_eq!(secansa.score(&cards, marker), Some(3));
+        assert_eq!(
+            secansa.describe(&cards, marker),
+            Some("secansa 5C 6C 7C".to_string())
+        );
+        assert_eq!(
+            registry.get("flor").unwrap().describe(&cards, marker),
+            Some("flor 38 5C 6C 7C".to_string())
+        );
+        assert_eq!(registry.get("ali").unwrap().score(&cards, marker), None);
+        assert!(registry.get("colors").is_none());
+    }
+
+    #[test]
+    fn register_joc() {
+        let mut registry = JocRegistry::default();
+        registry.register::<Colors>();
+        registry.register::<Colors>();
+        assert_eq!(registry.iter().count(), 4);
+
+        let colors = registry.get("colors").unwrap();
+        let cards = parse_cards("5B 1E RB").unwrap();
+        assert_eq!(colors.score(&cards, "4O".parse().unwrap()), Some(2));
+        assert_eq!(
+            colors.describe(&cards, "4O".parse().unwrap()),
+            Some("black".to_string())
+        );
+    }
+
+    #[test]
+    fn round_scores_registered_joc() {
+        let mut game = Game::new(vec![Player::new("a"), Player::new("b"), Player::new("c")]);
+        game.register_joc::<Colors>();
+        let mut round = Round::new(&game, &game.players()[2], deck::Deck::default()).unwrap();
+        round.seats[0].face_up_cards = parse_cards("5B 1E RB").unwrap();
+        round.seats[1].face_up_cards = parse_cards("5O 1C RO").unwrap();
+        round.seats[2].face_up_cards = parse_cards("6B 2B SB").unwrap();
+
+        let colors = game.jocs().get("colors").unwrap();
+        assert_eq!(colors.winner(&round), Some(Team::Team2));
+        assert_eq!(colors.team_score(&round, Team::Team1), Ok(4));
+
+        let scores = round.joc_scores().unwrap();
+        assert_eq!(scores.len(), 4);
+        assert_eq!(scores[0], ("flor", Some(RoundScoreSection(Team::Team1, 3))));
+        assert_eq!(
+            scores[3],
+            ("colors", Some(RoundScoreSection(Team::Team2, 2)))
+        );
+    }
+
+    #[test]
+    fn round_scores_replaced_joc() {
+        let mut game = Game::new(vec![Player::new("a"), Player::new("b"), Player::new("c")]);
+        game.register_joc::<RedAli>();
+        let mut round = Round::new(&game, &game.players()[2], deck::Deck::default()).unwrap();
+        round.seats[0].face_up_cards = parse_cards("1O 1B 1E").unwrap();
+        round.seats[1].face_up_cards = parse_cards("5O 1C RO").unwrap();
+        round.seats[2].face_up_cards = parse_cards("6B 2B SB").unwrap();
+        round
+            .set_ali_bet(scorers::ali::AgreedBet::Announced(None))
+            .unwrap();
+
+        // Seat 0's aces are no ali under the house rule, the score and bet go to the red hand
+        assert_eq!(
+            round.get_round_score().unwrap().ali,
+            Some(RoundScoreSection(Team::Team2, 1))
+        );
+    }
+}
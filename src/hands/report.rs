@@ -0,0 +1,117 @@
+use deck::{Card, Value};
+use hands::ali::Ali;
+use hands::flor::Flor;
+use hands::secansa::Secansa;
+use hands::Hand;
+use scorers::truc::{truc_rank, TrucValue};
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct HandReport {
+    pub flor: Option<Flor>,
+    pub secansa: Option<Secansa>,
+    pub ali: Option<Ali>,
+    pub reyes: usize,
+    // Truc rank of each card, in the same order as the analyzed cards
+    pub truc_ranks: Vec<(Card, TrucValue)>,
+    pub has_perico: bool,
+    pub has_perica: bool,
+}
+
+pub fn analyze(cards: &[Card], marker: Card) -> HandReport {
+    //! Everything a seat holds: jocs, reyes, truc ranks and wildcards.
+
+    HandReport {
+        flor: Flor::from_cards(cards, marker),
+        secansa: Secansa::from_cards(cards, marker),
+        ali: Ali::from_cards(cards, marker),
+        reyes: cards.iter().filter(|card| card.value == Value::Rey).count(),
+        truc_ranks: cards
+            .iter()
+            .map(|&card| (card, truc_rank(card, marker)))
+            .collect(),
+        has_perico: cards.iter().any(|card| card.is_perico(marker)),
+        has_perica: cards.iter().any(|card| card.is_perica(marker)),
+    }
+}
+
+impl HandReport {
+    pub fn score(&self) -> u8 {
+        //! Points the jocs in the hand are worth when announced and won.
+
+        self.flor.as_ref().map_or(0, |flor| flor.score())
+            + self.secansa.as_ref().map_or(0, |secansa| secansa.score())
+            + self.ali.as_ref().map_or(0, |ali| ali.score())
+    }
+
+    pub fn best_truc_card(&self) -> Option<Card> {
+        self.truc_ranks
+            .iter()
+            .max_by_key(|&&(_, rank)| rank)
+            .map(|&(card, _)| card)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use deck::parse_cards;
+
+    #[test]
+    fn analyze_no_jocs() {
+        let marker = "4B".parse().unwrap();
+        let cards = parse_cards("1O 3C 6E").unwrap();
+        let report = analyze(&cards, marker);
+
+        assert_eq!(report.flor, None);
+        assert_eq!(report.secansa, None);
+        assert_eq!(report.ali, None);
+        assert_eq!(report.reyes, 0);
+        assert!(!report.has_perico);
+        assert!(!report.has_perica);
+        assert_eq!(report.score(), 0);
+        assert_eq!(
+            report.truc_ranks,
+            vec![
+                ("1O".parse().unwrap(), TrucValue::AsBobo),
+                ("3C".parse().unwrap(), TrucValue::Tres),
+                ("6E".parse().unwrap(), TrucValue::Seis),
+            ]
+        );
+        assert_eq!(report.best_truc_card(), Some("3C".parse().unwrap()));
+    }
+
+    #[test]
+    fn analyze_secansa_and_ali() {
+        // Sota de bastos is the perica
+        let marker = "4B".parse().unwrap();
+        let cards = parse_cards("RB SB RE").unwrap();
+        let report = analyze(&cards, marker);
+
+        // Espadas breaks the flor
+        assert_eq!(report.flor.as_ref().map(|flor| flor.value()), None);
+        assert_eq!(
+            report.secansa.as_ref().map(|s| s.highest_value()),
+            Some(Value::Rey)
+        );
+        assert_eq!(report.ali.as_ref().map(|ali| ali.value()), Some(Value::Rey));
+        assert_eq!(report.reyes, 2);
+        assert!(report.has_perica);
+        assert!(!report.has_perico);
+        assert_eq!(report.best_truc_card(), Some("SB".parse().unwrap()));
+    }
+
+    #[test]
+    fn analyze_flor() {
+        // Caballo de oros is the perico
+        let marker = "4O".parse().unwrap();
+        let cards = parse_cards("7C CO 5C").unwrap();
+        let report = analyze(&cards, marker);
+
+        assert_eq!(report.flor.as_ref().map(|flor| flor.value()), Some(40));
+        assert_eq!(report.flor.as_ref().map(|flor| flor.score()), Some(3));
+        assert!(report.has_perico);
+        assert_eq!(report.truc_ranks[1].1, TrucValue::Perico);
+        // Flor 3, the 5-6-7 secansa 3 and the pair of sevens 1
+        assert_eq!(report.score(), 7);
+    }
+}
@@ -1,33 +1,129 @@
-use deck::Card;
-use hands::Hand;
+use deck::{Card, Value, VALUES};
+use hands::{describe_cards, split_wildcards, Hand, Wildcard};
+use itertools::Itertools;
 use std::cmp::Ordering;
 
-#[derive(Debug, Eq, PartialEq)]
+#[derive(Debug, Clone, Eq, PartialEq)]
 pub struct Secansa {
+    // Sorted by the value each card takes in the secansa
     cards: Vec<Card>,
+    wildcards: Vec<Wildcard>,
 }
 
-impl<'a> Hand<'a> for Secansa {
-    fn from_cards(cards: &[Card], _marker: Card) -> Option<Self> {
-        Secansa::from_cards_slice(cards)
+impl Hand for Secansa {
+    const NAME: &'static str = "secansa";
+
+    fn from_cards(cards: &[Card], marker: Card) -> Option<Self> {
+        let (naturals, wildcards) = split_wildcards(cards, marker);
+        Secansa::from_naturals_and_wildcards(&naturals, &wildcards)
+    }
+
+    fn score(&self) -> u8 {
+        if self.is_secansa_3_cards() {
+            3
+        } else {
+            1
+        }
+    }
+
+    fn describe(&self) -> String {
+        describe_cards(Self::NAME, &self.cards, &self.wildcards)
     }
 }
 
 impl Secansa {
+    #[cfg(test)]
     fn from_cards_slice(cards: &[Card]) -> Option<Self> {
-        if let Some(cards) = Self::sorted_secansa_cards(cards) {
-            Some(Secansa { cards })
+        Self::from_naturals_and_wildcards(cards, &[])
+    }
+
+    fn from_naturals_and_wildcards(naturals: &[Card], wildcards: &[Card]) -> Option<Self> {
+        //! Try every value for each wildcard and keep the best secansa.
+        //! Wildcards never repeat a value already in the hand, as that can't help a secansa.
+
+        let assignments = if wildcards.is_empty() {
+            vec![vec![]]
         } else {
-            None
+            wildcards
+                .iter()
+                .map(|_| VALUES.iter().cloned())
+                .multi_cartesian_product()
+                .filter(|values| {
+                    values.iter().enumerate().all(|(i, value)| {
+                        !values[..i].contains(value)
+                            && !naturals.iter().any(|card| card.value == *value)
+                    })
+                })
+                .collect()
+        };
+
+        let mut best: Option<Secansa> = None;
+        for values in assignments {
+            let candidate = Self::with_wildcard_values(naturals, wildcards, &values);
+            if candidate > best {
+                best = candidate;
+            }
         }
+        best
+    }
+
+    fn with_wildcard_values(
+        naturals: &[Card],
+        wildcards: &[Card],
+        values: &[Value],
+    ) -> Option<Self> {
+        let wildcards = wildcards
+            .iter()
+            .zip(values)
+            .map(|(&card, &value)| Wildcard { card, value })
+            .collect::<Vec<_>>();
+        let standing_cards = naturals
+            .iter()
+            .cloned()
+            .chain(wildcards.iter().map(|wildcard| Card {
+                suit: wildcard.card.suit,
+                value: wildcard.value,
+            }))
+            .collect::<Vec<_>>();
+
+        // Values are unique, so each card in the secansa can be traced back to a natural or a wildcard
+        let run = Self::sorted_secansa_cards(&standing_cards)?;
+        let cards = run
+            .iter()
+            .map(|standing| {
+                wildcards
+                    .iter()
+                    .find(|wildcard| wildcard.value == standing.value)
+                    .map_or(*standing, |wildcard| wildcard.card)
+            })
+            .collect();
+        let wildcards = wildcards
+            .into_iter()
+            .filter(|wildcard| run.iter().any(|card| card.value == wildcard.value))
+            .collect();
+        Some(Secansa { cards, wildcards })
+    }
+
+    pub fn cards(&self) -> &[Card] {
+        &self.cards
+    }
+
+    pub fn wildcards(&self) -> &[Wildcard] {
+        //! Wildcards used in the secansa, along with the value each one took.
+
+        &self.wildcards
     }
 
     fn is_secansa_3_cards(&self) -> bool {
         self.cards.len() == 3
     }
 
-    fn highest_card(&self) -> &Card {
-        self.cards.last().unwrap()
+    pub fn highest_value(&self) -> Value {
+        let card = self.cards.last().unwrap();
+        self.wildcards
+            .iter()
+            .find(|wildcard| wildcard.card == *card)
+            .map_or(card.value, |wildcard| wildcard.value)
     }
 
     fn sorted_secansa_cards(cards: &[Card]) -> Option<Vec<Card>> {
@@ -54,14 +150,6 @@ impl Secansa {
             None
         }
     }
-
-    pub fn score(&self) -> u8 {
-        if self.is_secansa_3_cards() {
-            3
-        } else {
-            1
-        }
-    }
 }
 
 impl Ord for Secansa {
@@ -71,8 +159,8 @@ impl Ord for Secansa {
         } else if !self.is_secansa_3_cards() && other.is_secansa_3_cards() {
             Ordering::Less
         } else {
-            let self_max = self.highest_card().value;
-            let other_max = other.highest_card().value;
+            let self_max = self.highest_value();
+            let other_max = other.highest_value();
             self_max.cmp(&other_max)
         }
     }
@@ -87,7 +175,7 @@ impl PartialOrd for Secansa {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use deck::{Suit, Value};
+    use deck::{parse_cards, Suit, Value};
 
     #[test]
     fn is_secansa_3_true() {
@@ -365,4 +453,71 @@ mod tests {
         result.sort();
         assert_eq!(expected, result);
     }
+
+    #[test]
+    fn secansa_wildcard_fills_gap() {
+        // Caballo de oros is the perico
+        let marker = "1O".parse().unwrap();
+        let hand = parse_cards("4C 6B CO").unwrap();
+        let secansa = Secansa::from_cards(&hand, marker).unwrap();
+
+        assert!(secansa.is_secansa_3_cards());
+        assert_eq!(secansa.score(), 3);
+        assert_eq!(secansa.cards(), &parse_cards("4C CO 6B").unwrap()[..]);
+        assert_eq!(secansa.describe(), "secansa 4C CO(5) 6B");
+        assert_eq!(
+            secansa.wildcards(),
+            &[Wildcard {
+                card: "CO".parse().unwrap(),
+                value: Value::Cinco,
+            }]
+        );
+    }
+
+    #[test]
+    fn secansa_wildcard_extends_run() {
+        // Sota de oros is the perica
+        let marker = "1O".parse().unwrap();
+        let hand = parse_cards("5C 5B SO").unwrap();
+        let secansa = Secansa::from_cards(&hand, marker).unwrap();
+
+        assert!(!secansa.is_secansa_3_cards());
+        assert_eq!(secansa.highest_value(), Value::Seis);
+        assert_eq!(secansa.wildcards()[0].value, Value::Seis);
+    }
+
+    #[test]
+    fn secansa_wildcard_keeps_own_value() {
+        // Rey de copas is the perico when the caballo is the marker
+        let marker = "CC".parse().unwrap();
+        let hand = parse_cards("SB RC RB").unwrap();
+        let secansa = Secansa::from_cards(&hand, marker).unwrap();
+
+        assert!(secansa.is_secansa_3_cards());
+        assert_eq!(secansa.highest_value(), Value::Rey);
+        assert_eq!(secansa.wildcards()[0].value, Value::Caballo);
+    }
+
+    #[test]
+    fn secansa_two_wildcards() {
+        let marker = "1O".parse().unwrap();
+        let hand = parse_cards("1C SO CO").unwrap();
+        let secansa = Secansa::from_cards(&hand, marker).unwrap();
+
+        assert!(secansa.is_secansa_3_cards());
+        assert_eq!(secansa.highest_value(), Value::Tres);
+        assert_eq!(secansa.wildcards().len(), 2);
+    }
+
+    #[test]
+    fn secansa_without_wildcards_ignores_marker() {
+        let marker = "1O".parse().unwrap();
+        let hand = parse_cards("1C 3B 5E").unwrap();
+        assert!(Secansa::from_cards(&hand, marker).is_none());
+
+        let hand = parse_cards("SC CB 5E").unwrap();
+        let secansa = Secansa::from_cards(&hand, marker).unwrap();
+        assert!(secansa.wildcards().is_empty());
+        assert_eq!(secansa, Secansa::from_cards_slice(&hand).unwrap());
+    }
 }
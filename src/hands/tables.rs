@@ -0,0 +1,277 @@
+use deck::{self, Card, Suit, Value};
+use hands::ali::Ali;
+use hands::flor::Flor;
+use hands::secansa::Secansa;
+use hands::Hand;
+use itertools::Itertools;
+use scorers::truc::{truc_rank, TrucValue};
+use std::sync::OnceLock;
+
+const CARDS: usize = 40;
+// Number of 3-card hands, 40 choose 3
+const HANDS: usize = 9880;
+// Jocs only depend on the marker's suit and on whether it is a sota, a caballo or anything else
+const MARKER_CLASSES: usize = 12;
+
+static GLOBAL: OnceLock<EvalTables> = OnceLock::new();
+
+// Lookup tables for 3-card hands, to evaluate hands without allocating.
+// Ranks compare like the hands they stand for, 0 meaning the hand has no such joc.
+pub struct EvalTables {
+    flor: Vec<u8>,
+    secansa: Vec<u8>,
+    ali: Vec<u8>,
+    truc: Vec<TrucValue>,
+}
+
+impl EvalTables {
+    pub fn new() -> Self {
+        //! Build the tables from the Flor, Secansa, Ali and truc implementations.
+
+        let mut tables = EvalTables {
+            flor: vec![0; HANDS * MARKER_CLASSES],
+            secansa: vec![0; HANDS * MARKER_CLASSES],
+            ali: vec![0; HANDS * MARKER_CLASSES],
+            truc: Vec::with_capacity(CARDS * CARDS),
+        };
+
+        let cards = deck::all_cards().collect::<Vec<_>>();
+        for marker in class_markers() {
+            for hand in cards.iter().cloned().combinations(3) {
+                let index = table_index([hand[0], hand[1], hand[2]], marker);
+                tables.flor[index] = Flor::from_cards(&hand, marker).map_or(0, |flor| flor.value());
+                tables.secansa[index] =
+                    Secansa::from_cards(&hand, marker).map_or(0, |s| secansa_rank(&s));
+                tables.ali[index] = Ali::from_cards(&hand, marker).map_or(0, |ali| ali_rank(&ali));
+            }
+        }
+
+        let mut truc = vec![TrucValue::Cuatro; CARDS * CARDS];
+        for (&marker, &card) in iproduct!(cards.iter(), cards.iter()) {
+            truc[card_index(marker) * CARDS + card_index(card)] = truc_rank(card, marker);
+        }
+        tables.truc = truc;
+
+        tables
+    }
+
+    pub fn global() -> &'static EvalTables {
+        //! Tables shared by the whole process, built on first use.
+
+        GLOBAL.get_or_init(EvalTables::new)
+    }
+
+    pub fn flor_value(&self, hand: [Card; 3], marker: Card) -> Option<u8> {
+        non_zero(self.flor[table_index(hand, marker)])
+    }
+
+    pub fn secansa_rank(&self, hand: [Card; 3], marker: Card) -> Option<u8> {
+        non_zero(self.secansa[table_index(hand, marker)])
+    }
+
+    pub fn ali_rank(&self, hand: [Card; 3], marker: Card) -> Option<u8> {
+        non_zero(self.ali[table_index(hand, marker)])
+    }
+
+    pub fn truc_rank(&self, card: Card, marker: Card) -> TrucValue {
+        self.truc[card_index(marker) * CARDS + card_index(card)]
+    }
+
+    pub fn truc_ranks(&self, hand: [Card; 3], marker: Card) -> [TrucValue; 3] {
+        [
+            self.truc_rank(hand[0], marker),
+            self.truc_rank(hand[1], marker),
+            self.truc_rank(hand[2], marker),
+        ]
+    }
+}
+
+impl Default for EvalTables {
+    fn default() -> Self {
+        EvalTables::new()
+    }
+}
+
+fn non_zero(rank: u8) -> Option<u8> {
+    match rank {
+        0 => None,
+        rank => Some(rank),
+    }
+}
+
+fn secansa_rank(secansa: &Secansa) -> u8 {
+    let length = if secansa.cards().len() == 3 { 16 } else { 0 };
+    length + secansa.highest_value() as u8 + 1
+}
+
+fn ali_rank(ali: &Ali) -> u8 {
+    let length = if ali.cards().len() == 3 { 16 } else { 0 };
+    // Aces are the best ali
+    let value = match ali.value() {
+        Value::Uno => Value::Rey as u8 + 1,
+        value => value as u8,
+    };
+    length + value + 1
+}
+
+fn card_index(card: Card) -> usize {
+    deck::deck_index(card) as usize
+}
+
+fn marker_class(marker: Card) -> usize {
+    let kind = match marker.value {
+        Value::Sota => 1,
+        Value::Caballo => 2,
+        _ => 0,
+    };
+    marker.suit as usize * 3 + kind
+}
+
+fn class_markers() -> Vec<Card> {
+    //! One marker for every marker class.
+
+    let suits = [Suit::Bastos, Suit::Copas, Suit::Oros, Suit::Espadas];
+    let values = [Value::Uno, Value::Sota, Value::Caballo];
+    iproduct!(suits.iter(), values.iter())
+        .map(|(&suit, &value)| Card { suit, value })
+        .collect()
+}
+
+fn hand_index(hand: [Card; 3]) -> usize {
+    //! Position of the hand among all 3-card hands, regardless of the order of its cards.
+
+    let mut indexes = [
+        card_index(hand[0]),
+        card_index(hand[1]),
+        card_index(hand[2]),
+    ];
+    indexes.sort();
+    debug_assert!(indexes[0] < indexes[1] && indexes[1] < indexes[2]);
+    let [a, b, c] = indexes;
+    a + b * (b - 1) / 2 + c * (c - 1) * (c - 2) / 6
+}
+
+fn table_index(hand: [Card; 3], marker: Card) -> usize {
+    marker_class(marker) * HANDS + hand_index(hand)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use deck::parse_cards;
+
+    fn hand(cards: &str) -> [Card; 3] {
+        let cards = parse_cards(cards).unwrap();
+        [cards[0], cards[1], cards[2]]
+    }
+
+    #[test]
+    fn hand_index_is_a_bijection() {
+        let mut seen = vec![false; HANDS];
+        for cards in deck::all_cards().combinations(3) {
+            let index = hand_index([cards[0], cards[1], cards[2]]);
+            assert!(!seen[index]);
+            seen[index] = true;
+            assert_eq!(index, hand_index([cards[2], cards[0], cards[1]]));
+        }
+        assert!(seen.iter().all(|&seen| seen));
+    }
+
+    #[test]
+    fn marker_classes() {
+        let classes = class_markers()
+            .into_iter()
+            .map(marker_class)
+            .collect::<Vec<_>>();
+        assert_eq!(classes, (0..MARKER_CLASSES).collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn tables_lookup() {
+        let tables = EvalTables::global();
+        // Caballo de oros is the perico
+        let marker = "4O".parse().unwrap();
+
+        assert_eq!(tables.flor_value(hand("7C CO 5C"), marker), Some(40));
+        assert_eq!(tables.flor_value(hand("7C CB 5C"), marker), None);
+        assert_eq!(tables.secansa_rank(hand("1C 3B 5E"), marker), None);
+        assert!(
+            tables.secansa_rank(hand("4C CO 6B"), marker)
+                > tables.secansa_rank(hand("SC CB 1E"), marker)
+        );
+        assert!(
+            tables.ali_rank(hand("1C 1B 5E"), marker) > tables.ali_rank(hand("RC RB 5E"), marker)
+        );
+        assert!(
+            tables.ali_rank(hand("5C 5B CO"), marker) > tables.ali_rank(hand("1C 1B 5E"), marker)
+        );
+        assert_eq!(
+            tables.truc_ranks(hand("CO 1E 4B"), marker),
+            [TrucValue::Perico, TrucValue::AsEspadas, TrucValue::Cuatro]
+        );
+    }
+
+    #[test]
+    fn tables_match_implementations() {
+        // Every marker, so the marker classes are checked too, against a sample of hands
+        let tables = EvalTables::global();
+        let cards = deck::all_cards().collect::<Vec<_>>();
+        for marker in cards.iter().cloned() {
+            for hand in cards
+                .iter()
+                .cloned()
+                .filter(|&card| card != marker)
+                .combinations(3)
+                .step_by(7)
+            {
+                let key = [hand[0], hand[1], hand[2]];
+                assert_eq!(
+                    tables.flor_value(key, marker),
+                    Flor::from_cards(&hand, marker).map(|flor| flor.value())
+                );
+                assert_eq!(
+                    tables.secansa_rank(key, marker).is_some(),
+                    Secansa::from_cards(&hand, marker).is_some()
+                );
+                assert_eq!(
+                    tables.ali_rank(key, marker).is_some(),
+                    Ali::from_cards(&hand, marker).is_some()
+                );
+                for &card in &hand {
+                    assert_eq!(tables.truc_rank(card, marker), truc_rank(card, marker));
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn table_ranks_order_like_hands() {
+        // Compare a sample of secansas and alis against each other under a single marker
+        let tables = EvalTables::global();
+        let marker = "SE".parse().unwrap();
+        let hands = deck::all_cards()
+            .combinations(3)
+            .step_by(37)
+            .map(|cards| {
+                let key = [cards[0], cards[1], cards[2]];
+                let secansa = Secansa::from_cards(&cards, marker);
+                let ali = Ali::from_cards(&cards, marker);
+                (key, secansa, ali)
+            })
+            .collect::<Vec<_>>();
+        for (a, b) in iproduct!(hands.iter(), hands.iter()) {
+            assert_eq!(
+                tables
+                    .secansa_rank(a.0, marker)
+                    .cmp(&tables.secansa_rank(b.0, marker)),
+                a.1.cmp(&b.1)
+            );
+            assert_eq!(
+                tables
+                    .ali_rank(a.0, marker)
+                    .cmp(&tables.ali_rank(b.0, marker)),
+                a.2.cmp(&b.2)
+            );
+        }
+    }
+}
@@ -1,39 +1,75 @@
 #[macro_use]
 extern crate itertools;
 extern crate rand;
-
-mod deck;
-mod hands;
+#[cfg(feature = "serde")]
+extern crate serde;
+#[cfg(feature = "serde")]
+#[macro_use]
+extern crate serde_derive;
+#[cfg(all(test, feature = "serde"))]
+extern crate serde_json;
+
+pub mod betting;
+pub mod cardset;
+pub mod deck;
+mod error;
+pub mod hands;
 pub mod scoreboard;
-mod scorers;
+pub mod scoresheet;
+pub mod scorers;
+#[cfg(test)]
 mod test_runner;
 
-use hands::Hand;
+pub use error::{Error, Result};
+use hands::{Hand, HandComparison, JocRegistry, RankedHand};
+use rand::Rng;
 use scorers::Scorer;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+// Cards dealt to each seat at the start of a round
+const CARDS_PER_SEAT: usize = 3;
+
+// Tells games apart, so a finished round is only annotated on its own game
+static NEXT_GAME_ID: AtomicUsize = AtomicUsize::new(0);
 
 #[derive(Debug, Eq, PartialEq, Copy, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub enum Team {
     Team1,
     Team2,
 }
 
+impl Team {
+    pub fn opponent(self) -> Team {
+        match self {
+            Team::Team1 => Team::Team2,
+            Team::Team2 => Team::Team1,
+        }
+    }
+}
+
 #[derive(Debug, Eq, PartialEq)]
-struct Player {
+pub struct Player {
     name: String,
 }
 
 impl Player {
-    fn new(name: &str) -> Self {
+    pub fn new(name: &str) -> Self {
         Self {
             name: name.to_owned(),
         }
     }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
 }
 
 #[derive(Clone)]
-struct Seat<'a> {
+pub struct Seat<'a> {
     player: &'a Player,
-    hand: Vec<deck::Card>,
+    hand: cardset::CardSet,
+    // In the order they were shown
     face_up_cards: Vec<deck::Card>,
 }
 
@@ -41,63 +77,208 @@ impl<'a> Seat<'a> {
     fn new(player: &'a Player) -> Self {
         Self {
             player,
-            hand: Vec::default(),
+            hand: cardset::CardSet::empty(),
             face_up_cards: Vec::default(),
         }
     }
 
+    pub fn player(&self) -> &'a Player {
+        self.player
+    }
+
+    pub fn hand(&self) -> cardset::CardSet {
+        self.hand
+    }
+
+    pub fn face_up_cards(&self) -> &[deck::Card] {
+        &self.face_up_cards
+    }
+
+    pub fn face_up_set(&self) -> cardset::CardSet {
+        cardset::CardSet::from(&self.face_up_cards[..])
+    }
+
     fn get_team(&self, seat_number: u8) -> Team {
-        if seat_number % 2 == 0 {
-            Team::Team1
-        } else {
-            Team::Team2
+        match seat_number % 2 {
+            0 => Team::Team1,
+            _ => Team::Team2,
         }
     }
 
-    fn discard(&mut self, card: deck::Card) -> Option<deck::Card> {
-        if let Some(pos) = self.hand.iter().position(|x| *x == card) {
-            Some(self.hand.remove(pos))
+    fn discard(&mut self, card: deck::Card) -> Result<deck::Card> {
+        if self.hand.remove(card) {
+            Ok(card)
         } else {
-            None
+            Err(Error::CardNotInHand(card))
         }
     }
 
-    fn show_card(&mut self, card: deck::Card) -> Option<deck::Card> {
-        if self.discard(card).is_some() {
-            self.face_up_cards.push(card);
-            Some(card)
-        } else {
-            None
-        }
+    fn show_card(&mut self, card: deck::Card) -> Result<deck::Card> {
+        self.discard(card)?;
+        self.face_up_cards.push(card);
+        Ok(card)
     }
 }
 
-struct Game {
+pub struct Game {
+    id: usize,
     players: Vec<Player>,
     scoreboard: scoreboard::Scoreboard,
+    // Rounds annotated so far
+    rounds: usize,
+    // Position of the dealer of the last annotated round, if any
+    dealer: Option<usize>,
+    jocs: JocRegistry,
 }
 
 impl Game {
-    fn new(players: Vec<Player>) -> Self {
+    pub fn new(players: Vec<Player>) -> Self {
         Game {
+            id: NEXT_GAME_ID.fetch_add(1, Ordering::Relaxed),
             players,
             scoreboard: scoreboard::Scoreboard::default(),
+            rounds: 0,
+            dealer: None,
+            jocs: JocRegistry::default(),
         }
     }
+
+    pub fn jocs(&self) -> &JocRegistry {
+        &self.jocs
+    }
+
+    pub fn register_joc<H: Hand + 'static>(&mut self) {
+        //! Add a house rules joc. The scoreboard only has sections for flor, secansa and ali, so
+        //! only a joc registered under one of those names replaces it, bets included, when scoring
+        //! rounds. Any other joc is neither bet on nor annotated, see `Round::joc_scores`.
+
+        self.jocs.register::<H>();
+    }
+
+    pub fn dealer(&self) -> Option<&Player> {
+        //! The dealer of the last annotated round, if any.
+
+        self.dealer.map(|pos| &self.players[pos])
+    }
+
+    pub fn winner(&self) -> Result<Option<Team>> {
+        self.scoreboard.winner()
+    }
+
+    pub fn next_round(&self) -> Result<Round<'_>> {
+        self.next_round_with(&mut rand::thread_rng())
+    }
+
+    pub fn next_round_with<R: Rng + ?Sized>(&self, rng: &mut R) -> Result<Round<'_>> {
+        //! Start the next round with a freshly shuffled deck and the cards already dealt.
+        //! The first dealer is picked at random, then the deal passes on in table order once each
+        //! round is annotated, so a round that is dropped is dealt again by the same seat.
+
+        if self.winner()?.is_some() {
+            return Err(Error::GameFinished);
+        }
+        if self.players.is_empty() {
+            return Err(Error::DealerNotSeated);
+        }
+        let dealer = match self.dealer {
+            Some(pos) => (pos + 1) % self.players.len(),
+            None => rng.gen_range(0, self.players.len()),
+        };
+
+        let deck = deck::Deck::shuffled_with(rng);
+        let mut round = Round::new(self, &self.players[dealer], deck)?;
+        round.deal(CARDS_PER_SEAT)?;
+        Ok(round)
+    }
+
+    pub fn players(&self) -> &[Player] {
+        &self.players
+    }
+
+    pub fn scoreboard(&self) -> &scoreboard::Scoreboard {
+        &self.scoreboard
+    }
+
+    pub fn annotate(&mut self, round: FinishedRound) -> Result<RoundSummary> {
+        //! Annotate the score of a finished round on the scoreboard, see `Round::finish`.
+        //! The round must have been dealt from this game since the last round was annotated.
+
+        if round.game != self.id || round.number != self.rounds {
+            return Err(Error::ForeignRound);
+        }
+        let score = round.score;
+        let events = self.scoreboard.annotate(score)?;
+        self.rounds += 1;
+        self.dealer = Some(round.dealer);
+
+        let mut summary = RoundSummary {
+            score,
+            cama_winner: None,
+            coto_winner: None,
+            game_winner: None,
+            events,
+        };
+        for event in &summary.events {
+            match *event {
+                scoreboard::ScoreboardEvent::CamaWon { winner, .. } => {
+                    summary.cama_winner = Some(winner)
+                }
+                scoreboard::ScoreboardEvent::CotoWon { winner, .. } => {
+                    summary.coto_winner = Some(winner)
+                }
+                scoreboard::ScoreboardEvent::GameWon { winner, .. } => {
+                    summary.game_winner = Some(winner)
+                }
+            }
+        }
+        Ok(summary)
+    }
+}
+
+// The score of a finished round, waiting to be annotated on its game
+#[derive(Debug, PartialEq)]
+pub struct FinishedRound {
+    game: usize,
+    // Rounds annotated on the game before this one
+    number: usize,
+    dealer: usize,
+    score: scoreboard::RoundScore,
+}
+
+impl FinishedRound {
+    pub fn score(&self) -> scoreboard::RoundScore {
+        self.score
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct RoundSummary {
+    pub score: scoreboard::RoundScore,
+    // Set when annotating the round closed a cama, a coto or the whole game
+    pub cama_winner: Option<Team>,
+    pub coto_winner: Option<Team>,
+    pub game_winner: Option<Team>,
+    pub events: Vec<scoreboard::ScoreboardEvent>,
 }
 
 #[derive(Debug, Copy, Clone, Eq, PartialEq)]
-struct GameBet<T> {
-    winner: Option<Team>,
-    agreed_bet: T,
+pub struct Play {
+    pub seat: usize,
+    pub card: deck::Card,
 }
 
+#[derive(Clone)]
 pub struct Round<'a> {
     game: &'a Game,
     seats: Vec<Seat<'a>>,
-    dealer: &'a Player,
+    dealer: usize,
     deck: deck::Deck,
     marker: deck::Card,
+    bazas: Vec<Vec<Play>>,
+    truc: betting::truc::TrucNegotiation,
+    flor: betting::envit::FlorNegotiation,
+    secansa: betting::envit::SecansaNegotiation,
+    ali: betting::envit::AliNegotiation,
     flor_scorer: scorers::flor::FlorScorer,
     secansa_scorer: scorers::secansa::SecansaScorer,
     ali_scorer: scorers::ali::AliScorer,
@@ -105,81 +286,328 @@ pub struct Round<'a> {
 }
 
 impl<'a> Round<'a> {
-    fn new(game: &'a Game, dealer: &'a Player, mut deck: deck::Deck) -> Self {
-        let seats = game.players.iter().map(Seat::new).collect();
-        Self {
+    pub fn new(game: &'a Game, dealer: &'a Player, mut deck: deck::Deck) -> Result<Self> {
+        let seats = game.players.iter().map(Seat::new).collect::<Vec<_>>();
+        let dealer = seats
+            .iter()
+            .position(|seat| seat.player == dealer)
+            .ok_or(Error::DealerNotSeated)?;
+        let marker = deck.draw().ok_or(Error::NotEnoughCards)?;
+        Ok(Self {
             game,
             seats,
             dealer,
-            marker: deck.draw().unwrap(),
+            marker,
             deck,
+            bazas: Vec::new(),
+            truc: Default::default(),
+            flor: Default::default(),
+            secansa: Default::default(),
+            ali: Default::default(),
             flor_scorer: Default::default(),
             secansa_scorer: Default::default(),
             ali_scorer: Default::default(),
             truc_scorer: Default::default(),
-        }
+        })
+    }
+
+    pub fn seats(&self) -> &[Seat<'a>] {
+        &self.seats
+    }
+
+    pub fn marker(&self) -> deck::Card {
+        self.marker
+    }
+
+    pub fn remaining_cards(&self) -> usize {
+        self.deck.remaining_cards()
+    }
+
+    pub fn remaining_card_set(&self) -> cardset::CardSet {
+        self.deck.card_set()
     }
 
-    fn dealer_position(&self) -> usize {
+    pub fn cards_in_play(&self) -> cardset::CardSet {
+        //! Cards held, shown or played by any seat.
+
+        let played = self.bazas.iter().flatten().map(|play| play.card);
         self.seats
             .iter()
-            .position(|seat| seat.player == self.dealer)
-            .expect("Round not properly set up: dealer is not seated")
+            .map(|seat| seat.hand | seat.face_up_set())
+            .fold(played.collect(), |acc, cards| acc | cards)
+    }
+
+    pub fn seat_team(&self, seat: usize) -> Option<Team> {
+        self.seats
+            .get(seat)
+            .map(|seat_ref| seat_ref.get_team(seat as u8))
     }
 
-    fn deal(&mut self, num_cards: usize) {
+    pub fn dealer_position(&self) -> usize {
+        self.dealer
+    }
+
+    pub fn deal(&mut self, num_cards: usize) -> Result<()> {
+        if self.deck.remaining_cards() < num_cards * self.seats.len() {
+            return Err(Error::NotEnoughCards);
+        }
         for _ in 0..num_cards {
             for seat in &mut self.seats {
                 if let Some(card) = self.deck.draw() {
-                    seat.hand.push(card);
+                    seat.hand.insert(card);
                 }
             }
         }
+        debug_assert_eq!(self.validate(), Ok(()));
+        Ok(())
+    }
+
+    pub fn bazas(&self) -> &[Vec<Play>] {
+        &self.bazas
+    }
+
+    #[cfg(test)]
+    pub(crate) fn play_face_up_cards(&mut self) {
+        //! Record the cards already face up as bazas, the nth card of every seat in the nth one.
+
+        let bazas = (0..CARDS_PER_SEAT)
+            .map(|i| {
+                self.seats
+                    .iter()
+                    .enumerate()
+                    .filter_map(|(seat, seat_ref)| {
+                        seat_ref
+                            .face_up_cards
+                            .get(i)
+                            .map(|&card| Play { seat, card })
+                    })
+                    .collect::<Vec<_>>()
+            })
+            .filter(|baza| !baza.is_empty())
+            .collect();
+        self.bazas = bazas;
+    }
+
+    pub fn current_baza(&self) -> &[Play] {
+        match self.bazas.last() {
+            Some(baza) if baza.len() < self.seats.len() => baza,
+            _ => &[],
+        }
+    }
+
+    pub fn current_baza_number(&self) -> usize {
+        self.bazas
+            .iter()
+            .filter(|baza| baza.len() == self.seats.len())
+            .count()
+    }
+
+    pub fn baza_leader(&self) -> usize {
+        //! The hand leads the first baza. Every other baza is led by whoever played the highest
+        //! card in the previous one, the first of them to play in case of parda.
+
+        match self.bazas.last() {
+            Some(baza) if baza.len() < self.seats.len() => baza[0].seat,
+            Some(baza) => scorers::truc::winning_play(baza, self)
+                .map(|play| play.seat)
+                .expect("Completed bazas are never empty"),
+            None => (self.dealer_position() + 1) % self.seats.len(),
+        }
+    }
+
+    pub fn next_to_play(&self) -> Option<usize> {
+        if self.is_finished() {
+            return None;
+        }
+        Some((self.baza_leader() + self.current_baza().len()) % self.seats.len())
+    }
+
+    pub fn play_card(&mut self, seat: usize, card: deck::Card) -> Result<deck::Card> {
+        //! Play a card on the current baza. Only the seat returned by `next_to_play` may play.
+
+        if self.next_to_play() != Some(seat) {
+            return Err(Error::OutOfTurn(seat));
+        }
+        if self.is_bet_pending() {
+            return Err(Error::BetPending);
+        }
+        let card = self.seats[seat].show_card(card)?;
+
+        let play = Play { seat, card };
+        let num_seats = self.seats.len();
+        match self.bazas.last_mut() {
+            Some(baza) if baza.len() < num_seats => baza.push(play),
+            _ => self.bazas.push(vec![play]),
+        }
+
+        debug_assert_eq!(self.validate(), Ok(()));
+        Ok(card)
     }
 
-    fn is_finished(&self) -> bool {
-        self.seats.iter().all(|seat| seat.hand.is_empty())
+    pub fn validate(&self) -> Result<()> {
+        //! Check that the deck, the marker and every seat's hand and face up cards make up the
+        //! 40-card deck exactly once, that every seat was dealt as many cards and that the dealer
+        //! is seated. Debug builds check this whenever cards are dealt, played or scored.
+
+        if self.dealer >= self.seats.len() {
+            return Err(Error::DealerNotSeated);
+        }
+
+        let seat_cards = self
+            .seats
+            .iter()
+            .flat_map(|seat| seat.hand.iter().chain(seat.face_up_cards.iter().cloned()));
+        let mut seen = self.deck.card_set();
+        for card in Some(self.marker).into_iter().chain(seat_cards) {
+            if !seen.insert(card) {
+                return Err(Error::DuplicateCard(card));
+            }
+        }
+        if let Some(card) = (!seen).iter().next() {
+            return Err(Error::MissingCard(card));
+        }
+
+        let dealt = |seat: &Seat| seat.hand.len() + seat.face_up_cards.len();
+        if self
+            .seats
+            .iter()
+            .any(|seat| dealt(seat) != dealt(&self.seats[0]))
+        {
+            return Err(Error::UnevenHands);
+        }
+        Ok(())
     }
 
-    // fn compute_game_winners(&mut self) -> Result<(), RoundNotFinishedError> {}
+    pub fn is_finished(&self) -> bool {
+        //! A round ends once every card dealt is played, or as soon as a truc call is declined.
+
+        let dealt = self
+            .seats
+            .iter()
+            .any(|seat| !seat.hand.is_empty() || !seat.face_up_cards.is_empty());
+        let played = self.seats.iter().all(|seat| seat.hand.is_empty());
+        dealt && (played || self.truc.bet().winner().is_some())
+    }
 
     fn set_flor_bet(&mut self, agreed_bet: scorers::flor::AgreedBet) {
         self.flor_scorer.set_bet(agreed_bet);
     }
 
-    fn set_secansa_bet(&mut self, agreed_bet: scorers::secansa::AgreedBet) {
+    fn set_secansa_bet(&mut self, agreed_bet: scorers::secansa::AgreedBet) -> Result<()> {
         self.secansa_scorer.set_bet(agreed_bet)
     }
 
-    fn set_ali_bet(&mut self, agreed_bet: scorers::ali::AgreedBet) {
+    fn set_ali_bet(&mut self, agreed_bet: scorers::ali::AgreedBet) -> Result<()> {
         self.ali_scorer.set_bet(agreed_bet)
     }
 
-    fn set_truc_bet(&mut self, agreed_bet: scorers::truc::Bet) {
-        self.truc_scorer.set_bet(agreed_bet)
+    fn is_bet_pending(&self) -> bool {
+        self.truc.awaiting_response().is_some()
+            || self.flor.to_speak().is_some()
+            || self.secansa.to_speak().is_some()
+            || self.ali.to_speak().is_some()
     }
 
-    fn get_round_score(&self) -> scoreboard::RoundScore {
-        scoreboard::RoundScore {
-            flor: self.flor_scorer.get_score(self),
-            secansa: self.secansa_scorer.get_score(self),
-            ali: self.ali_scorer.get_score(self),
-            rey: scorers::rey::ReyScorer::default().get_score(self),
-            truc: self.truc_scorer.get_score(self),
+    fn envit_team(&self, seat: usize, action: betting::envit::EnvitAction) -> Result<Team> {
+        let team = self.seat_team(seat).ok_or(Error::IllegalBet)?;
+        if action == betting::envit::EnvitAction::Announce && self.is_finished() {
+            return Err(Error::IllegalBet);
         }
+        Ok(team)
     }
 
-    fn iter_with_team(&self) -> impl Iterator<Item = (Team, &Seat)> {
-        self.seats
-            .iter()
+    pub fn flor_negotiation(&self) -> &betting::envit::FlorNegotiation {
+        &self.flor
+    }
 
-            // Enumerate each seat's position
-            .enumerate()
+    pub fn flor(&mut self, seat: usize, action: betting::envit::EnvitAction) -> Result<()> {
+        //! Negotiate the flor on behalf of a seat's team. The agreed bet is scored once the
+        //! negotiation closes, and no cards can be played while a proposal waits for an answer.
 
-            .map(|(pos, seat)| {
-                let team = seat.get_team(pos as u8);
-                (team, seat)
-            })
+        let team = self.envit_team(seat, action)?;
+        self.flor.apply(team, action)?;
+        if let Some(agreed_bet) = self.flor.agreed_bet() {
+            self.set_flor_bet(agreed_bet);
+        }
+        Ok(())
+    }
+
+    pub fn secansa_negotiation(&self) -> &betting::envit::SecansaNegotiation {
+        &self.secansa
+    }
+
+    pub fn secansa(&mut self, seat: usize, action: betting::envit::EnvitAction) -> Result<()> {
+        //! Negotiate the secansa on behalf of a seat's team, see `flor`.
+
+        let team = self.envit_team(seat, action)?;
+        self.secansa.apply(team, action)?;
+        if let Some(agreed_bet) = self.secansa.agreed_bet() {
+            self.set_secansa_bet(agreed_bet)?;
+        }
+        Ok(())
+    }
+
+    pub fn ali_negotiation(&self) -> &betting::envit::AliNegotiation {
+        &self.ali
+    }
+
+    pub fn ali(&mut self, seat: usize, action: betting::envit::EnvitAction) -> Result<()> {
+        //! Negotiate the ali on behalf of a seat's team, see `flor`.
+
+        let team = self.envit_team(seat, action)?;
+        self.ali.apply(team, action)?;
+        if let Some(agreed_bet) = self.ali.agreed_bet() {
+            self.set_ali_bet(agreed_bet)?;
+        }
+        Ok(())
+    }
+
+    pub fn truc_negotiation(&self) -> &betting::truc::TrucNegotiation {
+        &self.truc
+    }
+
+    pub fn truc(&mut self, seat: usize, action: betting::truc::TrucAction) -> Result<()> {
+        //! Negotiate the truc on behalf of a seat's team. Calls are only allowed between bazas.
+
+        let team = self.seat_team(seat).ok_or(Error::IllegalBet)?;
+        if action == betting::truc::TrucAction::Call
+            && (!self.current_baza().is_empty() || self.is_finished())
+        {
+            return Err(Error::IllegalBet);
+        }
+        self.truc.apply(team, action)?;
+        self.truc_scorer.set_bet(self.truc.bet());
+        Ok(())
+    }
+
+    pub fn get_round_score(&self) -> Result<scoreboard::RoundScore> {
+        Ok(scoreboard::RoundScore {
+            flor: self.flor_scorer.get_score(self)?,
+            secansa: self.secansa_scorer.get_score(self)?,
+            ali: self.ali_scorer.get_score(self)?,
+            rey: scorers::rey::ReyScorer.get_score(self)?,
+            truc: self.truc_scorer.get_score(self)?,
+        })
+    }
+
+    pub fn finish(mut self) -> Result<FinishedRound> {
+        //! Score a finished round, to be annotated with `Game::annotate` once the round is over.
+
+        if !self.is_finished() {
+            return Err(Error::RoundNotFinished);
+        }
+        // Cards left unplayed after a declined truc are shown, so jocs count whole hands
+        for seat in &mut self.seats {
+            seat.face_up_cards.extend(seat.hand.iter());
+            seat.hand = cardset::CardSet::empty();
+        }
+        debug_assert_eq!(self.validate(), Ok(()));
+        Ok(FinishedRound {
+            game: self.game.id,
+            number: self.game.rounds,
+            dealer: self.dealer,
+            score: self.get_round_score()?,
+        })
     }
 
     fn iter_from_hand(&'a self) -> impl Iterator<Item = (Team, &'a Seat<'a>)> {
@@ -200,31 +628,62 @@ impl<'a> Round<'a> {
             })
     }
 
-    fn get_winner_from_cards<'b, H>(&'b self) -> Option<Team>
-    where
-        H: Hand<'b>,
-    {
+    fn get_winner_from_cards<H: Hand>(&self) -> Option<Team> {
+        self.compare_hands::<H>().winner()
+    }
+
+    pub fn compare_hands<H: Hand>(&self) -> HandComparison<H> {
+        //! Rank every seat holding the hand in its face up cards.
+        //! On ties, the seat closest to the hand wins.
+
+        let seats_from_hand = (0..self.seats.len())
+            .cycle()
+            .skip(self.dealer_position() + 1)
+            .take(self.seats.len());
+
         // Remove any seats without the hand
-        let mut hands_by_team = self
-            .iter_from_hand()
-            .filter_map(|(team, seat)| {
-                let face_up_cards = &seat.face_up_cards;
-                let hand = H::from_cards(face_up_cards, self.marker);
-                match hand {
-                    Some(hand) => Some((team, hand)),
-                    None => None,
-                }
+        let hands = seats_from_hand
+            .filter_map(|pos| {
+                let seat = &self.seats[pos];
+                H::from_cards(&seat.face_up_cards, self.marker).map(|hand| RankedHand {
+                    seat: pos,
+                    team: seat.get_team(pos as u8),
+                    hand,
+                })
             })
-            .collect::<Vec<_>>();
+            .collect();
+        HandComparison::new(hands)
+    }
+
+    fn team_hand_score<H: Hand>(&self, team: Team) -> Result<scoreboard::Points> {
+        self.iter_from_hand()
+            .filter(|&(seat_team, _)| seat_team == team)
+            .filter_map(|(_, seat)| H::from_cards(&seat.face_up_cards, self.marker))
+            .try_fold(0, |total: scoreboard::Points, hand| {
+                total
+                    .checked_add(scoreboard::Points::from(hand.score()))
+                    .ok_or(Error::ScoreOverflow)
+            })
+    }
 
-        // Iterator::max_by returns the last element that matches. We need the first.
-        hands_by_team.reverse();
+    pub fn joc_scores(&self) -> Result<Vec<(&'static str, Option<scoreboard::RoundScoreSection>)>> {
+        //! Winner of every registered joc and the points its team holds, before any bets.
+        //! This is only a report, the round score comes from the flor, secansa and ali scorers.
 
-        hands_by_team
-            .into_iter()
-            // https://stackoverflow.com/a/49312019/2221217
-            .max_by(|&(_, ref hand1), &(_, ref hand2)| hand1.cmp(hand2))
-            .map(|(team, _)| team)
+        self.game
+            .jocs()
+            .iter()
+            .map(|joc| {
+                let section = match joc.winner(self) {
+                    Some(team) => Some(scoreboard::RoundScoreSection(
+                        team,
+                        joc.team_score(self, team)?,
+                    )),
+                    None => None,
+                };
+                Ok((joc.name(), section))
+            })
+            .collect()
     }
 }
 
@@ -255,7 +714,7 @@ mod tests {
             Player::new("e"),
             Player::new("f"),
         ]);
-        let mut round = Round::new(&game, &game.players[0], deck::Deck::default());
+        let mut round = Round::new(&game, &game.players[0], deck::Deck::default()).unwrap();
         assert_eq!(round.deck.remaining_cards(), 39);
         let mut cards = Vec::default();
         while let Some(card) = round.deck.draw() {
@@ -264,6 +723,28 @@ mod tests {
         assert!(!cards.contains(&round.marker));
     }
 
+    #[test]
+    fn round_new_dealer_not_seated() {
+        let game = Game::new(vec![Player::new("a"), Player::new("b")]);
+        let stranger = Player::new("c");
+        let result = Round::new(&game, &stranger, deck::Deck::default());
+        assert_eq!(result.err(), Some(Error::DealerNotSeated));
+    }
+
+    #[test]
+    fn round_deal_not_enough_cards() {
+        let game = Game::new(vec![
+            Player::new("a"),
+            Player::new("b"),
+            Player::new("c"),
+            Player::new("d"),
+        ]);
+        let mut round = Round::new(&game, &game.players[0], deck::Deck::default()).unwrap();
+        assert_eq!(round.deal(10), Err(Error::NotEnoughCards));
+        assert_eq!(round.remaining_cards(), 39);
+        assert!(round.deal(9).is_ok());
+    }
+
     #[test]
     fn dealer_position() {
         let game = Game::new(vec![
@@ -276,11 +757,514 @@ mod tests {
         ]);
         let deck = deck::Deck::default();
         for i in 0..6 {
-            let round = Round::new(&game, &game.players[i], deck.clone());
+            let round = Round::new(&game, &game.players[i], deck.clone()).unwrap();
             assert_eq!(round.dealer_position(), i);
         }
     }
 
+    #[test]
+    fn round_play_card() {
+        let game = Game::new(vec![Player::new("a"), Player::new("b")]);
+        let mut round = Round::new(&game, &game.players()[0], deck::Deck::default()).unwrap();
+        round.deal(3).unwrap();
+        let card = round.seats()[1].hand().iter().next().unwrap();
+
+        assert_eq!(
+            round.play_card(1, round.marker()),
+            Err(Error::CardNotInHand(round.marker()))
+        );
+        assert_eq!(round.play_card(2, card), Err(Error::OutOfTurn(2)));
+        assert_eq!(round.play_card(1, card), Ok(card));
+        assert_eq!(round.seats()[1].hand().len(), 2);
+        assert_eq!(round.seats()[1].face_up_cards(), &[card]);
+        assert_eq!(round.current_baza(), &[Play { seat: 1, card }]);
+    }
+
+    #[test]
+    fn round_play_card_out_of_turn() {
+        let game = Game::new(vec![Player::new("a"), Player::new("b"), Player::new("c")]);
+        let mut round = Round::new(&game, &game.players()[1], deck::Deck::default()).unwrap();
+        round.deal(3).unwrap();
+
+        // The hand is the seat after the dealer
+        assert_eq!(round.next_to_play(), Some(2));
+        let card = round.seats()[0].hand().iter().next().unwrap();
+        assert_eq!(round.play_card(0, card), Err(Error::OutOfTurn(0)));
+        assert_eq!(round.seats()[0].hand().len(), 3);
+        assert!(round.current_baza().is_empty());
+
+        let card = round.seats()[2].hand().iter().next().unwrap();
+        assert!(round.play_card(2, card).is_ok());
+        assert_eq!(round.next_to_play(), Some(0));
+    }
+
+    #[test]
+    fn round_baza_winner_leads_next_baza() {
+        let game = Game::new(vec![Player::new("a"), Player::new("b"), Player::new("c")]);
+        let mut round = Round::new(&game, &game.players()[0], deck::Deck::default()).unwrap();
+        let card = |value, suit| deck::Card { value, suit };
+        seat_cards(&mut round, "1O", &["4C 5C", "4B 3B", "1E 6B"]);
+
+        assert_eq!(round.current_baza_number(), 0);
+        assert_eq!(round.baza_leader(), 1);
+        round
+            .play_card(1, card(deck::Value::Cuatro, deck::Suit::Bastos))
+            .unwrap();
+        round
+            .play_card(2, card(deck::Value::Uno, deck::Suit::Espadas))
+            .unwrap();
+        round
+            .play_card(0, card(deck::Value::Cuatro, deck::Suit::Copas))
+            .unwrap();
+
+        // As d'Espases wins the first baza
+        assert_eq!(round.current_baza_number(), 1);
+        assert!(round.current_baza().is_empty());
+        assert_eq!(round.baza_leader(), 2);
+        assert_eq!(round.next_to_play(), Some(2));
+        round
+            .play_card(2, card(deck::Value::Seis, deck::Suit::Bastos))
+            .unwrap();
+        assert_eq!(round.baza_leader(), 2);
+        assert_eq!(round.next_to_play(), Some(0));
+        round
+            .play_card(0, card(deck::Value::Cinco, deck::Suit::Copas))
+            .unwrap();
+        round
+            .play_card(1, card(deck::Value::Tres, deck::Suit::Bastos))
+            .unwrap();
+
+        assert_eq!(round.bazas().len(), 2);
+        assert!(round.is_finished());
+        assert_eq!(round.next_to_play(), None);
+    }
+
+    #[test]
+    fn round_baza_parda_first_highest_leads() {
+        let game = Game::new(vec![Player::new("a"), Player::new("b")]);
+        let mut round = Round::new(&game, &game.players()[0], deck::Deck::default()).unwrap();
+        let card = |value, suit| deck::Card { value, suit };
+        seat_cards(&mut round, "1O", &["3C 5C", "3B 5B"]);
+
+        round
+            .play_card(1, card(deck::Value::Tres, deck::Suit::Bastos))
+            .unwrap();
+        round
+            .play_card(0, card(deck::Value::Tres, deck::Suit::Copas))
+            .unwrap();
+        assert_eq!(round.baza_leader(), 1);
+    }
+
+    #[test]
+    fn round_truc_between_bazas() {
+        use betting::truc::TrucAction;
+
+        let game = Game::new(vec![Player::new("a"), Player::new("b")]);
+        let mut round = Round::new(&game, &game.players[0], deck::Deck::default()).unwrap();
+        round.deal(3).unwrap();
+
+        round.truc(1, TrucAction::Call).unwrap();
+        let card = round.seats[1].hand.iter().next().unwrap();
+        assert_eq!(round.play_card(1, card), Err(Error::BetPending));
+        assert_eq!(round.truc(1, TrucAction::Accept), Err(Error::IllegalBet));
+        round.truc(0, TrucAction::Accept).unwrap();
+        assert_eq!(
+            round.truc_negotiation().bet(),
+            scorers::truc::Bet::Truc(None)
+        );
+
+        // Team1 was challenged, so only Team1 can raise, and only between bazas
+        round.play_card(1, card).unwrap();
+        assert_eq!(round.truc(0, TrucAction::Call), Err(Error::IllegalBet));
+        let card = round.seats[0].hand.iter().next().unwrap();
+        round.play_card(0, card).unwrap();
+        assert_eq!(round.truc(1, TrucAction::Call), Err(Error::IllegalBet));
+        round.truc(0, TrucAction::Call).unwrap();
+        round.truc(1, TrucAction::Reject).unwrap();
+
+        assert_eq!(
+            round.truc_negotiation().bet(),
+            scorers::truc::Bet::Retruc(Some(Team::Team1))
+        );
+        // The declined call decides the truc regardless of the bazas
+        assert_eq!(
+            round.get_round_score().unwrap().truc,
+            Some(scoreboard::RoundScoreSection(Team::Team1, 3))
+        );
+
+        // and ends the round, with cards still in hand
+        assert!(round.is_finished());
+        assert_eq!(round.next_to_play(), None);
+        let card = round.seats[1].hand.iter().next().unwrap();
+        assert_eq!(round.play_card(1, card), Err(Error::OutOfTurn(1)));
+        assert_eq!(
+            round.finish().unwrap().score().truc,
+            Some(scoreboard::RoundScoreSection(Team::Team1, 3))
+        );
+    }
+
+    #[test]
+    fn round_secansa_negotiation() {
+        use betting::envit::{EnvitAction, EnvitLevel};
+
+        let game = Game::new(vec![Player::new("a"), Player::new("b")]);
+        let mut round = Round::new(&game, &game.players[0], deck::Deck::default()).unwrap();
+        seat_cards(&mut round, "4E", &["3C 4O 5B", "1O 6B RB"]);
+
+        round.secansa(0, EnvitAction::Announce).unwrap();
+        let card = "1O".parse().unwrap();
+        assert_eq!(round.play_card(1, card), Err(Error::BetPending));
+
+        // Only Team2 can answer, and vals start at tres val
+        let envit = EnvitAction::Raise(EnvitLevel::Envit);
+        assert_eq!(round.secansa(0, envit), Err(Error::IllegalBet));
+        let val = EnvitAction::Raise(EnvitLevel::Val(2));
+        assert_eq!(round.secansa(1, val), Err(Error::IllegalBet));
+        round.secansa(1, envit).unwrap();
+        round
+            .secansa(0, EnvitAction::Raise(EnvitLevel::Val(3)))
+            .unwrap();
+        round.secansa(1, EnvitAction::Reject).unwrap();
+        assert_eq!(
+            round.secansa_negotiation().agreed_bet(),
+            Some(scorers::secansa::AgreedBet::Envit(Some(Team::Team1)))
+        );
+        assert_eq!(
+            round.secansa(1, EnvitAction::Announce),
+            Err(Error::IllegalBet)
+        );
+
+        while let Some(seat) = round.next_to_play() {
+            let card = round.seats[seat].hand.iter().next().unwrap();
+            round.play_card(seat, card).unwrap();
+        }
+        assert_eq!(
+            round.finish().unwrap().score().secansa,
+            Some(scoreboard::RoundScoreSection(Team::Team1, 4))
+        );
+    }
+
+    #[test]
+    fn round_finish_not_finished() {
+        let game = Game::new(vec![Player::new("a"), Player::new("b")]);
+        let mut round = Round::new(&game, &game.players[0], deck::Deck::default()).unwrap();
+        round.deal(3).unwrap();
+        assert_eq!(round.finish().err(), Some(Error::RoundNotFinished));
+        assert_eq!(game.scoreboard().current_cama_score().unwrap().max(), 0);
+
+        // A round never dealt has no cards left to play, but isn't finished either
+        let round = Round::new(&game, &game.players[0], deck::Deck::default()).unwrap();
+        assert!(!round.is_finished());
+        assert_eq!(round.finish().err(), Some(Error::RoundNotFinished));
+    }
+
+    fn seat_cards(round: &mut Round, marker: &str, hands: &[&str]) {
+        //! Deal the given marker and hands, leaving every other card in the deck.
+
+        let mut deck = deck::Deck::default();
+        round.marker = marker.parse().unwrap();
+        deck.remove(round.marker);
+        for (seat, hand) in round.seats.iter_mut().zip(hands) {
+            seat.hand = deck::parse_cards(hand).unwrap().into();
+            for card in seat.hand.iter() {
+                assert!(deck.remove(card));
+            }
+        }
+        round.deck = deck;
+    }
+
+    fn finish_round_with_rey(game: &mut Game) -> RoundSummary {
+        // A single baza doesn't decide the truc, so only the rey scores
+        let mut round = Round::new(game, &game.players[0], deck::Deck::default()).unwrap();
+        seat_cards(&mut round, "RE", &["RO", "4C"]);
+        round.play_card(1, "4C".parse().unwrap()).unwrap();
+        round.play_card(0, "RO".parse().unwrap()).unwrap();
+        let score = round.finish().unwrap();
+        game.annotate(score).unwrap()
+    }
+
+    #[test]
+    fn round_finish_annotates_scoreboard() {
+        let mut game = Game::new(vec![Player::new("a"), Player::new("b")]);
+        let summary = finish_round_with_rey(&mut game);
+
+        let expected = scoreboard::RoundScore {
+            rey: Some(scoreboard::RoundScoreSection(Team::Team1, 1)),
+            flor: None,
+            secansa: None,
+            ali: None,
+            truc: None,
+        };
+        assert_eq!(summary.score, expected);
+        assert_eq!(summary.cama_winner, None);
+        assert_eq!(summary.coto_winner, None);
+        assert_eq!(summary.game_winner, None);
+        assert!(summary.events.is_empty());
+        assert_eq!(game.scoreboard().current_cama_score().unwrap().max(), 1);
+    }
+
+    #[test]
+    fn round_finish_reading_scoreboard() {
+        let mut game = Game::new(vec![Player::new("a"), Player::new("b")]);
+        let mut round = Round::new(&game, &game.players[0], deck::Deck::default()).unwrap();
+        seat_cards(&mut round, "RE", &["RO", "4C"]);
+        round.play_card(1, "4C".parse().unwrap()).unwrap();
+        round.play_card(0, "RO".parse().unwrap()).unwrap();
+
+        // Finishing only scores the round, the scoreboard can be read meanwhile
+        let scoreboard = game.scoreboard();
+        let finished = round.finish().unwrap();
+        assert!(scoreboard.rounds().is_empty());
+
+        let score = finished.score();
+        game.annotate(finished).unwrap();
+        assert_eq!(game.scoreboard().rounds(), vec![score]);
+    }
+
+    #[test]
+    fn round_finish_foreign_round() {
+        let mut game = Game::new(vec![Player::new("a"), Player::new("b")]);
+        let mut other = Game::new(vec![Player::new("a"), Player::new("b")]);
+        let mut round = Round::new(&other, &other.players[0], deck::Deck::default()).unwrap();
+        seat_cards(&mut round, "RE", &["RO", "4C"]);
+        round.play_card(1, "4C".parse().unwrap()).unwrap();
+        round.play_card(0, "RO".parse().unwrap()).unwrap();
+
+        // The round is only annotated on its own game, and copies of it only once
+        let first = round.clone().finish().unwrap();
+        let second = round.clone().finish().unwrap();
+        let third = round.finish().unwrap();
+        assert_eq!(game.annotate(first).err(), Some(Error::ForeignRound));
+        other.annotate(second).unwrap();
+        assert_eq!(other.annotate(third).err(), Some(Error::ForeignRound));
+        assert_eq!(other.scoreboard().rounds().len(), 1);
+        assert!(game.scoreboard().rounds().is_empty());
+    }
+
+    #[test]
+    fn round_finish_wins_cama() {
+        let mut game = Game::new(vec![Player::new("a"), Player::new("b")]);
+        game.scoreboard
+            .annotate(scoreboard::RoundScore {
+                rey: None,
+                flor: None,
+                secansa: None,
+                ali: None,
+                truc: Some(scoreboard::RoundScoreSection(Team::Team1, 39)),
+            })
+            .unwrap();
+
+        let summary = finish_round_with_rey(&mut game);
+        assert_eq!(summary.cama_winner, Some(Team::Team1));
+        assert_eq!(summary.coto_winner, None);
+        assert_eq!(summary.game_winner, None);
+        assert_eq!(game.scoreboard().current_cama_score().unwrap().max(), 0);
+    }
+
+    #[test]
+    fn round_finish_wins_game() {
+        let mut game = Game::new(vec![Player::new("a"), Player::new("b")]);
+        game.scoreboard.game_config = scoreboard::GameConfig {
+            game_win_score: 1,
+            coto_win_score: 1,
+            cama_win_score: 1,
+            ..Default::default()
+        };
+
+        let summary = finish_round_with_rey(&mut game);
+        assert_eq!(summary.cama_winner, Some(Team::Team1));
+        assert_eq!(summary.coto_winner, Some(Team::Team1));
+        assert_eq!(summary.game_winner, Some(Team::Team1));
+        assert_eq!(summary.events.len(), 3);
+        assert_eq!(
+            summary.events[2],
+            scoreboard::ScoreboardEvent::GameWon {
+                winner: Team::Team1,
+                cotos: (1, 0),
+            }
+        );
+    }
+
+    #[test]
+    fn game_next_round_deals() {
+        let game = Game::new(vec![
+            Player::new("a"),
+            Player::new("b"),
+            Player::new("c"),
+            Player::new("d"),
+        ]);
+        let round = game.next_round().unwrap();
+        assert_eq!(round.remaining_cards(), 40 - 1 - 4 * CARDS_PER_SEAT);
+        for seat in round.seats() {
+            assert_eq!(seat.hand().len(), CARDS_PER_SEAT);
+        }
+    }
+
+    #[test]
+    fn game_next_round_with_seed() {
+        use rand::{SeedableRng, StdRng};
+
+        let game = Game::new(vec![Player::new("a"), Player::new("b")]);
+        let game2 = Game::new(vec![Player::new("a"), Player::new("b")]);
+        let mut rng = StdRng::seed_from_u64(3);
+        let mut rng2 = StdRng::seed_from_u64(3);
+        for _ in 0..3 {
+            let round = game.next_round_with(&mut rng).unwrap();
+            let round2 = game2.next_round_with(&mut rng2).unwrap();
+            assert_eq!(round.dealer_position(), round2.dealer_position());
+            assert_eq!(round.marker(), round2.marker());
+            assert_eq!(round.seats()[0].hand(), round2.seats()[0].hand());
+            assert_eq!(round.seats()[1].hand(), round2.seats()[1].hand());
+        }
+    }
+
+    #[test]
+    fn round_card_sets() {
+        let game = Game::new(vec![Player::new("a"), Player::new("b"), Player::new("c")]);
+        let mut round = game.next_round().unwrap();
+        let seat = round.next_to_play().unwrap();
+        let card = round.seats()[seat].hand().iter().next().unwrap();
+        round.play_card(seat, card).unwrap();
+
+        let in_play = round.cards_in_play();
+        assert_eq!(in_play.len(), 9);
+        assert!(in_play.contains(card));
+        assert!(!round.seats()[seat].hand().contains(card));
+        assert_eq!(round.seats()[seat].hand().len(), 2);
+        assert!((in_play & round.remaining_card_set()).is_empty());
+        assert!(!round.remaining_card_set().contains(round.marker()));
+        assert_eq!(round.remaining_card_set().len(), round.remaining_cards());
+    }
+
+    #[test]
+    fn round_clone() {
+        let game = Game::new(vec![Player::new("a"), Player::new("b")]);
+        let mut round = Round::new(&game, &game.players[0], deck::Deck::from_seed(1)).unwrap();
+        round.deal(3).unwrap();
+
+        // Playing on a copy, e.g. to explore a move, leaves the round untouched
+        let mut copy = round.clone();
+        let card = copy.seats[1].hand.iter().next().unwrap();
+        copy.play_card(1, card).unwrap();
+        assert!(round.seats[1].hand.contains(card));
+        assert!(round.bazas().is_empty());
+        assert_eq!(copy.remaining_cards(), round.remaining_cards());
+    }
+
+    #[test]
+    fn round_validate() {
+        let game = Game::new(vec![Player::new("a"), Player::new("b")]);
+        let mut round = Round::new(&game, &game.players[0], deck::Deck::default()).unwrap();
+        assert_eq!(round.validate(), Ok(()));
+        round.deal(3).unwrap();
+        assert_eq!(round.validate(), Ok(()));
+
+        // The marker in a hand too
+        let card = round.seats[0].hand.iter().next().unwrap();
+        round.seats[0].hand.remove(card);
+        round.seats[0].hand.insert(round.marker);
+        assert_eq!(round.validate(), Err(Error::DuplicateCard(round.marker)));
+        round.seats[0].hand.remove(round.marker);
+        assert_eq!(round.validate(), Err(Error::MissingCard(card)));
+        round.seats[1].face_up_cards.push(card);
+        assert_eq!(round.validate(), Err(Error::UnevenHands));
+        round.seats[1].face_up_cards.clear();
+        round.seats[0].face_up_cards.push(card);
+        assert_eq!(round.validate(), Ok(()));
+
+        round.dealer = 2;
+        assert_eq!(round.validate(), Err(Error::DealerNotSeated));
+    }
+
+    #[test]
+    fn game_next_round_rotates_dealer() {
+        let mut game = Game::new(vec![Player::new("a"), Player::new("b"), Player::new("c")]);
+        assert_eq!(game.dealer(), None);
+        let mut first = None;
+        for i in 0..7 {
+            let mut round = game.next_round().unwrap();
+            let dealer = round.dealer_position();
+            let first = *first.get_or_insert(dealer);
+            assert_eq!(dealer, (first + i) % 3);
+            while let Some(seat) = round.next_to_play() {
+                let card = round.seats[seat].hand.iter().next().unwrap();
+                round.play_card(seat, card).unwrap();
+            }
+            let finished = round.finish().unwrap();
+
+            // Dropping a round deals it again from the same seat
+            let round = game.next_round().unwrap();
+            if i > 0 {
+                assert_eq!(round.dealer_position(), dealer);
+            }
+            drop(round);
+
+            game.annotate(finished).unwrap();
+            assert_eq!(game.dealer(), Some(&game.players()[dealer]));
+        }
+    }
+
+    #[test]
+    fn game_next_round_game_finished() {
+        let mut game = Game::new(vec![Player::new("a"), Player::new("b")]);
+        game.scoreboard.game_config = scoreboard::GameConfig {
+            game_win_score: 1,
+            coto_win_score: 1,
+            cama_win_score: 1,
+            ..Default::default()
+        };
+        finish_round_with_rey(&mut game);
+
+        assert_eq!(game.winner(), Ok(Some(Team::Team1)));
+        assert_eq!(game.next_round().err(), Some(Error::GameFinished));
+    }
+
+    #[test]
+    fn game_play_until_winner() {
+        let mut game = Game::new(vec![
+            Player::new("a"),
+            Player::new("b"),
+            Player::new("c"),
+            Player::new("d"),
+        ]);
+        game.scoreboard.game_config = scoreboard::GameConfig {
+            game_win_score: 2,
+            coto_win_score: 2,
+            cama_win_score: 5,
+            ..Default::default()
+        };
+
+        let mut rounds = 0;
+        while game.winner().unwrap().is_none() {
+            let mut round = game.next_round().unwrap();
+            while let Some(seat) = round.next_to_play() {
+                let card = round.seats()[seat].hand().iter().next().unwrap();
+                round.play_card(seat, card).unwrap();
+            }
+            let score = round.finish().unwrap();
+            game.annotate(score).unwrap();
+            rounds += 1;
+        }
+        assert!(rounds >= 4);
+        assert_eq!(game.next_round().err(), Some(Error::GameFinished));
+    }
+
+    #[test]
+    fn round_seat_team() {
+        let game = Game::new(vec![
+            Player::new("a"),
+            Player::new("b"),
+            Player::new("c"),
+            Player::new("d"),
+        ]);
+        let round = Round::new(&game, &game.players()[0], deck::Deck::default()).unwrap();
+        assert_eq!(round.seat_team(0), Some(Team::Team1));
+        assert_eq!(round.seat_team(1), Some(Team::Team2));
+        assert_eq!(round.seat_team(2), Some(Team::Team1));
+        assert_eq!(round.seat_team(3), Some(Team::Team2));
+        assert_eq!(round.seat_team(4), None);
+    }
+
     #[test]
     fn round_deal() {
         let game = Game::new(vec![
@@ -291,13 +1275,13 @@ mod tests {
             Player::new("e"),
             Player::new("f"),
         ]);
-        let mut round = Round::new(&game, &game.players[0], deck::Deck::default());
+        let mut round = Round::new(&game, &game.players[0], deck::Deck::default()).unwrap();
         for i in 0..6 {
             assert_eq!(round.seats[i].hand.len(), 0);
             assert_eq!(round.seats[i].face_up_cards.len(), 0);
         }
         const NUM_CARDS: usize = 6;
-        round.deal(NUM_CARDS);
+        round.deal(NUM_CARDS).unwrap();
         for i in 0..6 {
             assert_eq!(round.seats[i].hand.len(), NUM_CARDS);
             assert_eq!(round.seats[i].face_up_cards.len(), 0);
@@ -311,14 +1295,16 @@ mod tests {
             hand: vec![deck::Card {
                 suit: deck::Suit::Bastos,
                 value: deck::Value::Caballo,
-            }],
+            }]
+            .into(),
             face_up_cards: Vec::new(),
         };
-        let result = seat.discard(deck::Card {
+        let card = deck::Card {
             suit: deck::Suit::Bastos,
             value: deck::Value::Cinco,
-        });
-        assert!(result.is_none());
+        };
+        let result = seat.discard(card);
+        assert_eq!(result, Err(Error::CardNotInHand(card)));
     }
 
     #[test]
@@ -329,15 +1315,14 @@ mod tests {
         };
         let mut seat = Seat {
             player: &Player::new("a"),
-            hand: vec![card],
+            hand: vec![card].into(),
             face_up_cards: Vec::new(),
         };
         let result = seat.discard(deck::Card {
             suit: deck::Suit::Bastos,
             value: deck::Value::Caballo,
         });
-        assert!(result.is_some());
-        assert_eq!(result.unwrap(), card);
+        assert_eq!(result, Ok(card));
     }
 
     #[test]
@@ -347,14 +1332,16 @@ mod tests {
             hand: vec![deck::Card {
                 suit: deck::Suit::Bastos,
                 value: deck::Value::Caballo,
-            }],
+            }]
+            .into(),
             face_up_cards: Vec::new(),
         };
-        let result = seat.show_card(deck::Card {
+        let card = deck::Card {
             suit: deck::Suit::Bastos,
             value: deck::Value::Cinco,
-        });
-        assert!(result.is_none());
+        };
+        let result = seat.show_card(card);
+        assert_eq!(result, Err(Error::CardNotInHand(card)));
         assert!(seat.face_up_cards.is_empty());
     }
 
@@ -366,15 +1353,14 @@ mod tests {
         };
         let mut seat = Seat {
             player: &Player::new("a"),
-            hand: vec![card],
+            hand: vec![card].into(),
             face_up_cards: Vec::new(),
         };
         let result = seat.show_card(deck::Card {
             suit: deck::Suit::Bastos,
             value: deck::Value::Caballo,
         });
-        assert!(result.is_some());
-        assert_eq!(result.unwrap(), card);
+        assert_eq!(result, Ok(card));
         assert_eq!(seat.face_up_cards.len(), 1);
         assert!(seat.face_up_cards.contains(&card));
     }
@@ -389,26 +1375,27 @@ mod tests {
             Player::new("e"),
             Player::new("f"),
         ]);
-        let mut round = Round::new(&game, &game.players[0], deck::Deck::default());
-        round.seats[0].hand.push(deck::Card {
+        let mut round = Round::new(&game, &game.players[0], deck::Deck::default()).unwrap();
+        round.seats[0].hand.insert(deck::Card {
             suit: deck::Suit::Bastos,
             value: deck::Value::Caballo,
         });
         assert!(!round.is_finished());
-        round.seats[0].hand.clear();
+        let card = round.seats[0].hand.iter().next().unwrap();
+        round.seats[0].show_card(card).unwrap();
         assert!(round.is_finished());
     }
 
     #[test]
     fn test_set_flor_bet() {
         let game = Game::new(vec![Player::new("a")]);
-        let mut round = Round::new(&game, &game.players[0], deck::Deck::default());
+        let mut round = Round::new(&game, &game.players[0], deck::Deck::default()).unwrap();
 
         round.seats = vec![
             // 34
             Seat {
                 player: &game.players[0],
-                hand: vec![],
+                hand: cardset::CardSet::empty(),
                 face_up_cards: vec![
                     deck::Card {
                         suit: deck::Suit::Copas,
@@ -427,7 +1414,7 @@ mod tests {
             // 34
             Seat {
                 player: &game.players[0],
-                hand: vec![],
+                hand: cardset::CardSet::empty(),
                 face_up_cards: vec![
                     deck::Card {
                         suit: deck::Suit::Copas,
@@ -446,13 +1433,13 @@ mod tests {
         ];
 
         // Before announcing
-        assert!(round.flor_scorer.get_score(&round).is_none());
+        assert!(round.flor_scorer.get_score(&round).unwrap().is_none());
 
         // After announcing
         round.set_flor_bet(scorers::flor::AgreedBet::Announced(None));
 
         if let Some(scoreboard::RoundScoreSection(_team, value)) =
-            round.flor_scorer.get_score(&round)
+            round.flor_scorer.get_score(&round).unwrap()
         {
             assert_eq!(value, 3);
         } else {
@@ -463,7 +1450,7 @@ mod tests {
         round.set_flor_bet(scorers::flor::AgreedBet::Envit(None));
 
         if let Some(scoreboard::RoundScoreSection(_team, value)) =
-            round.flor_scorer.get_score(&round)
+            round.flor_scorer.get_score(&round).unwrap()
         {
             assert_eq!(value, 6);
         } else {
@@ -474,11 +1461,11 @@ mod tests {
     #[test]
     fn test_set_secansa_bet() {
         let game = Game::new(vec![Player::new("a")]);
-        let mut round = Round::new(&game, &game.players[0], deck::Deck::default());
+        let mut round = Round::new(&game, &game.players[0], deck::Deck::default()).unwrap();
 
         round.seats = vec![Seat {
             player: &game.players[0],
-            hand: vec![],
+            hand: cardset::CardSet::empty(),
             face_up_cards: vec![
                 deck::Card {
                     suit: deck::Suit::Copas,
@@ -496,13 +1483,15 @@ mod tests {
         }];
 
         // Before announcing
-        assert!(round.secansa_scorer.get_score(&round).is_none());
+        assert!(round.secansa_scorer.get_score(&round).unwrap().is_none());
 
         // After announcing
-        round.set_secansa_bet(scorers::secansa::AgreedBet::Announced(None));
+        round
+            .set_secansa_bet(scorers::secansa::AgreedBet::Announced(None))
+            .unwrap();
 
         if let Some(scoreboard::RoundScoreSection(_team, value)) =
-            round.secansa_scorer.get_score(&round)
+            round.secansa_scorer.get_score(&round).unwrap()
         {
             assert_eq!(value, 3);
         } else {
@@ -510,10 +1499,12 @@ mod tests {
         };
 
         // After setting a bet
-        round.set_secansa_bet(scorers::secansa::AgreedBet::Envit(None));
+        round
+            .set_secansa_bet(scorers::secansa::AgreedBet::Envit(None))
+            .unwrap();
 
         if let Some(scoreboard::RoundScoreSection(_team, value)) =
-            round.secansa_scorer.get_score(&round)
+            round.secansa_scorer.get_score(&round).unwrap()
         {
             assert_eq!(value, 4);
         } else {
@@ -524,11 +1515,11 @@ mod tests {
     #[test]
     fn test_set_ali_bet() {
         let game = Game::new(vec![Player::new("a")]);
-        let mut round = Round::new(&game, &game.players[0], deck::Deck::default());
+        let mut round = Round::new(&game, &game.players[0], deck::Deck::default()).unwrap();
 
         round.seats = vec![Seat {
             player: &game.players[0],
-            hand: vec![],
+            hand: cardset::CardSet::empty(),
             face_up_cards: vec![
                 deck::Card {
                     suit: deck::Suit::Copas,
@@ -546,13 +1537,15 @@ mod tests {
         }];
 
         // Before announcing
-        assert!(round.ali_scorer.get_score(&round).is_none());
+        assert!(round.ali_scorer.get_score(&round).unwrap().is_none());
 
         // After announcing
-        round.set_ali_bet(scorers::ali::AgreedBet::Announced(None));
+        round
+            .set_ali_bet(scorers::ali::AgreedBet::Announced(None))
+            .unwrap();
 
         if let Some(scoreboard::RoundScoreSection(_team, value)) =
-            round.ali_scorer.get_score(&round)
+            round.ali_scorer.get_score(&round).unwrap()
         {
             assert_eq!(value, 3);
         } else {
@@ -560,10 +1553,12 @@ mod tests {
         };
 
         // After setting a bet
-        round.set_ali_bet(scorers::ali::AgreedBet::Envit(None));
+        round
+            .set_ali_bet(scorers::ali::AgreedBet::Envit(None))
+            .unwrap();
 
         if let Some(scoreboard::RoundScoreSection(_team, value)) =
-            round.ali_scorer.get_score(&round)
+            round.ali_scorer.get_score(&round).unwrap()
         {
             assert_eq!(value, 4);
         } else {
@@ -576,7 +1571,7 @@ mod tests {
     #[test]
     fn get_flor_winner_from_cards_no_flor() {
         let game = Game::new(vec![Player::new("a")]);
-        let mut round = Round::new(&game, &game.players[0], deck::Deck::default());
+        let mut round = Round::new(&game, &game.players[0], deck::Deck::default()).unwrap();
 
         round.marker = deck::Card {
             suit: deck::Suit::Oros,
@@ -587,7 +1582,7 @@ mod tests {
             // No flor
             Seat {
                 player: &game.players[0],
-                hand: vec![],
+                hand: cardset::CardSet::empty(),
                 face_up_cards: vec![
                     deck::Card {
                         suit: deck::Suit::Copas,
@@ -611,7 +1606,7 @@ mod tests {
     #[test]
     fn get_flor_winner_from_cards() {
         let game = Game::new(vec![Player::new("a")]);
-        let mut round = Round::new(&game, &game.players[0], deck::Deck::default());
+        let mut round = Round::new(&game, &game.players[0], deck::Deck::default()).unwrap();
 
         round.marker = deck::Card {
             suit: deck::Suit::Oros,
@@ -622,7 +1617,7 @@ mod tests {
             // No flor
             Seat {
                 player: &game.players[0],
-                hand: vec![],
+                hand: cardset::CardSet::empty(),
                 face_up_cards: vec![
                     deck::Card {
                         suit: deck::Suit::Copas,
@@ -641,7 +1636,7 @@ mod tests {
             // Low Flor
             Seat {
                 player: &game.players[0],
-                hand: vec![],
+                hand: cardset::CardSet::empty(),
                 face_up_cards: vec![
                     deck::Card {
                         suit: deck::Suit::Copas,
@@ -660,7 +1655,7 @@ mod tests {
             // Medium Flor
             Seat {
                 player: &game.players[0],
-                hand: vec![],
+                hand: cardset::CardSet::empty(),
                 face_up_cards: vec![
                     deck::Card {
                         suit: deck::Suit::Copas,
@@ -679,7 +1674,7 @@ mod tests {
             // High Flor
             Seat {
                 player: &game.players[0],
-                hand: vec![],
+                hand: cardset::CardSet::empty(),
                 face_up_cards: vec![
                     deck::Card {
                         suit: deck::Suit::Copas,
@@ -709,13 +1704,13 @@ mod tests {
             Player::new("c"),
             Player::new("d"),
         ]);
-        let mut round = Round::new(&game, &game.players[1], deck::Deck::default());
+        let mut round = Round::new(&game, &game.players[1], deck::Deck::default()).unwrap();
 
         round.seats = vec![
             // No flor
             Seat {
                 player: &game.players[0],
-                hand: vec![],
+                hand: cardset::CardSet::empty(),
                 face_up_cards: vec![
                     deck::Card {
                         suit: deck::Suit::Copas,
@@ -734,7 +1729,7 @@ mod tests {
             // 35
             Seat {
                 player: &game.players[1],
-                hand: vec![],
+                hand: cardset::CardSet::empty(),
                 face_up_cards: vec![
                     deck::Card {
                         suit: deck::Suit::Oros,
@@ -753,7 +1748,7 @@ mod tests {
             // 35
             Seat {
                 player: &game.players[2],
-                hand: vec![],
+                hand: cardset::CardSet::empty(),
                 face_up_cards: vec![
                     deck::Card {
                         suit: deck::Suit::Copas,
@@ -772,7 +1767,7 @@ mod tests {
             // 35
             Seat {
                 player: &game.players[3],
-                hand: vec![],
+                hand: cardset::CardSet::empty(),
                 face_up_cards: vec![
                     deck::Card {
                         suit: deck::Suit::Bastos,
@@ -799,13 +1794,13 @@ mod tests {
     #[test]
     fn get_secansa_winner_from_cards_no_secansa() {
         let game = Game::new(vec![Player::new("a")]);
-        let mut round = Round::new(&game, &game.players[0], deck::Deck::default());
+        let mut round = Round::new(&game, &game.players[0], deck::Deck::default()).unwrap();
 
         round.seats = vec![
             // No secansa
             Seat {
                 player: &game.players[0],
-                hand: vec![],
+                hand: cardset::CardSet::empty(),
                 face_up_cards: vec![
                     deck::Card {
                         suit: deck::Suit::Oros,
@@ -829,13 +1824,13 @@ mod tests {
     #[test]
     fn get_secansa_winner_from_cards() {
         let game = Game::new(vec![Player::new("a")]);
-        let mut round = Round::new(&game, &game.players[0], deck::Deck::default());
+        let mut round = Round::new(&game, &game.players[0], deck::Deck::default()).unwrap();
 
         round.seats = vec![
             // No secansa
             Seat {
                 player: &game.players[0],
-                hand: vec![],
+                hand: cardset::CardSet::empty(),
                 face_up_cards: vec![
                     deck::Card {
                         suit: deck::Suit::Oros,
@@ -854,7 +1849,7 @@ mod tests {
             // Two card secansa
             Seat {
                 player: &game.players[0],
-                hand: vec![],
+                hand: cardset::CardSet::empty(),
                 face_up_cards: vec![
                     deck::Card {
                         suit: deck::Suit::Oros,
@@ -873,7 +1868,7 @@ mod tests {
             // Secansa real
             Seat {
                 player: &game.players[0],
-                hand: vec![],
+                hand: cardset::CardSet::empty(),
                 face_up_cards: vec![
                     deck::Card {
                         suit: deck::Suit::Oros,
@@ -892,7 +1887,7 @@ mod tests {
             // Three card secansa
             Seat {
                 player: &game.players[0],
-                hand: vec![],
+                hand: cardset::CardSet::empty(),
                 face_up_cards: vec![
                     deck::Card {
                         suit: deck::Suit::Oros,
@@ -922,13 +1917,13 @@ mod tests {
             Player::new("c"),
             Player::new("d"),
         ]);
-        let mut round = Round::new(&game, &game.players[1], deck::Deck::default());
+        let mut round = Round::new(&game, &game.players[1], deck::Deck::default()).unwrap();
 
         round.seats = vec![
             // No secansa
             Seat {
                 player: &game.players[0],
-                hand: vec![],
+                hand: cardset::CardSet::empty(),
                 face_up_cards: vec![
                     deck::Card {
                         suit: deck::Suit::Oros,
@@ -947,7 +1942,7 @@ mod tests {
             // Secansa real
             Seat {
                 player: &game.players[1],
-                hand: vec![],
+                hand: cardset::CardSet::empty(),
                 face_up_cards: vec![
                     deck::Card {
                         suit: deck::Suit::Oros,
@@ -966,7 +1961,7 @@ mod tests {
             // Secansa real
             Seat {
                 player: &game.players[2],
-                hand: vec![],
+                hand: cardset::CardSet::empty(),
                 face_up_cards: vec![
                     deck::Card {
                         suit: deck::Suit::Oros,
@@ -985,7 +1980,7 @@ mod tests {
             // Secansa real
             Seat {
                 player: &game.players[3],
-                hand: vec![],
+                hand: cardset::CardSet::empty(),
                 face_up_cards: vec![
                     deck::Card {
                         suit: deck::Suit::Oros,
@@ -1012,13 +2007,13 @@ mod tests {
     #[test]
     fn get_ali_winner_from_cards_no_ali() {
         let game = Game::new(vec![Player::new("a")]);
-        let mut round = Round::new(&game, &game.players[0], deck::Deck::default());
+        let mut round = Round::new(&game, &game.players[0], deck::Deck::default()).unwrap();
 
         round.seats = vec![
             // No ali
             Seat {
                 player: &game.players[0],
-                hand: vec![],
+                hand: cardset::CardSet::empty(),
                 face_up_cards: vec![
                     deck::Card {
                         suit: deck::Suit::Oros,
@@ -1042,13 +2037,13 @@ mod tests {
     #[test]
     fn get_ali_winner_from_cards() {
         let game = Game::new(vec![Player::new("a")]);
-        let mut round = Round::new(&game, &game.players[0], deck::Deck::default());
+        let mut round = Round::new(&game, &game.players[0], deck::Deck::default()).unwrap();
 
         round.seats = vec![
             // No ali
             Seat {
                 player: &game.players[0],
-                hand: vec![],
+                hand: cardset::CardSet::empty(),
                 face_up_cards: vec![
                     deck::Card {
                         suit: deck::Suit::Oros,
@@ -1067,7 +2062,7 @@ mod tests {
             // Two card ali
             Seat {
                 player: &game.players[0],
-                hand: vec![],
+                hand: cardset::CardSet::empty(),
                 face_up_cards: vec![
                     deck::Card {
                         suit: deck::Suit::Oros,
@@ -1086,7 +2081,7 @@ mod tests {
             // ali aces
             Seat {
                 player: &game.players[0],
-                hand: vec![],
+                hand: cardset::CardSet::empty(),
                 face_up_cards: vec![
                     deck::Card {
                         suit: deck::Suit::Oros,
@@ -1105,7 +2100,7 @@ mod tests {
             // Three card ali
             Seat {
                 player: &game.players[0],
-                hand: vec![],
+                hand: cardset::CardSet::empty(),
                 face_up_cards: vec![
                     deck::Card {
                         suit: deck::Suit::Oros,
@@ -1135,13 +2130,13 @@ mod tests {
             Player::new("c"),
             Player::new("d"),
         ]);
-        let mut round = Round::new(&game, &game.players[1], deck::Deck::default());
+        let mut round = Round::new(&game, &game.players[1], deck::Deck::default()).unwrap();
 
         round.seats = vec![
             // No ali
             Seat {
                 player: &game.players[0],
-                hand: vec![],
+                hand: cardset::CardSet::empty(),
                 face_up_cards: vec![
                     deck::Card {
                         suit: deck::Suit::Oros,
@@ -1160,7 +2155,7 @@ mod tests {
             // ali aces
             Seat {
                 player: &game.players[1],
-                hand: vec![],
+                hand: cardset::CardSet::empty(),
                 face_up_cards: vec![
                     deck::Card {
                         suit: deck::Suit::Oros,
@@ -1179,7 +2174,7 @@ mod tests {
             // ali aces
             Seat {
                 player: &game.players[2],
-                hand: vec![],
+                hand: cardset::CardSet::empty(),
                 face_up_cards: vec![
                     deck::Card {
                         suit: deck::Suit::Oros,
@@ -1198,7 +2193,7 @@ mod tests {
             // ali aces
             Seat {
                 player: &game.players[3],
-                hand: vec![],
+                hand: cardset::CardSet::empty(),
                 face_up_cards: vec![
                     deck::Card {
                         suit: deck::Suit::Oros,
@@ -1220,6 +2215,34 @@ mod tests {
         assert_eq!(round.get_winner_from_cards::<ali::Ali>(), expected);
     }
 
+    #[test]
+    fn compare_hands_explains_tie_break() {
+        let game = Game::new(vec![
+            Player::new("a"),
+            Player::new("b"),
+            Player::new("c"),
+            Player::new("d"),
+        ]);
+        let mut round = Round::new(&game, &game.players[1], deck::Deck::default()).unwrap();
+        round.seats[0].face_up_cards = deck::parse_cards("1O 1B 1C").unwrap();
+        round.seats[1].face_up_cards = deck::parse_cards("3O 3B 5C").unwrap();
+        round.seats[2].face_up_cards = deck::parse_cards("4O 6B 2C").unwrap();
+        round.seats[3].face_up_cards = deck::parse_cards("1E 1C 1O").unwrap();
+
+        let comparison = round.compare_hands::<ali::Ali>();
+        let seats = comparison
+            .ranking()
+            .iter()
+            .map(|ranked| ranked.seat)
+            .collect::<Vec<_>>();
+        // Seat 2 is the hand and holds no ali, seat 3 ties with seat 0 but plays first
+        assert_eq!(seats, vec![3, 0, 1]);
+        assert_eq!(comparison.deciding_seat(), Some(3));
+        assert_eq!(comparison.winner(), Some(Team::Team2));
+        assert!(comparison.tie_break_used());
+        assert_eq!(round.get_winner_from_cards::<ali::Ali>(), Some(Team::Team2));
+    }
+
     #[test]
     fn get_round_score() {
         let game = Game::new(vec![
@@ -1228,13 +2251,13 @@ mod tests {
             Player::new("c"),
             Player::new("d"),
         ]);
-        let mut round = Round::new(&game, &game.players[1], deck::Deck::default());
+        let mut round = Round::new(&game, &game.players[1], deck::Deck::default()).unwrap();
 
         round.seats = vec![
-            // 39 flor, 7-sota secansa, perica
+            // 39 flor, 5-perica-7 secansa
             Seat {
                 player: &game.players[0],
-                hand: vec![],
+                hand: cardset::CardSet::empty(),
                 face_up_cards: vec![
                     deck::Card {
                         suit: deck::Suit::Oros,
@@ -1253,7 +2276,7 @@ mod tests {
             // ali aces
             Seat {
                 player: &game.players[1],
-                hand: vec![],
+                hand: cardset::CardSet::empty(),
                 face_up_cards: vec![
                     deck::Card {
                         suit: deck::Suit::Oros,
@@ -1272,7 +2295,7 @@ mod tests {
             // secansa real, perico
             Seat {
                 player: &game.players[2],
-                hand: vec![],
+                hand: cardset::CardSet::empty(),
                 face_up_cards: vec![
                     deck::Card {
                         suit: deck::Suit::Espadas,
@@ -1291,7 +2314,7 @@ mod tests {
             // secansa 3
             Seat {
                 player: &game.players[3],
-                hand: vec![],
+                hand: cardset::CardSet::empty(),
                 face_up_cards: vec![
                     deck::Card {
                         suit: deck::Suit::Oros,
@@ -1314,19 +2337,22 @@ mod tests {
             .set_bet(scorers::flor::AgreedBet::Announced(None));
         round
             .secansa_scorer
-            .set_bet(scorers::secansa::AgreedBet::Announced(None));
+            .set_bet(scorers::secansa::AgreedBet::Announced(None))
+            .unwrap();
         round
             .ali_scorer
-            .set_bet(scorers::ali::AgreedBet::Announced(None));
+            .set_bet(scorers::ali::AgreedBet::Announced(None))
+            .unwrap();
 
         let expected = scoreboard::RoundScore {
             flor: Some(scoreboard::RoundScoreSection(Team::Team1, 3)),
-            secansa: Some(scoreboard::RoundScoreSection(Team::Team1, 4)),
+            secansa: Some(scoreboard::RoundScoreSection(Team::Team1, 6)),
             ali: Some(scoreboard::RoundScoreSection(Team::Team2, 6)),
             rey: Some(scoreboard::RoundScoreSection(Team::Team1, 1)),
             truc: Some(scoreboard::RoundScoreSection(Team::Team1, 1)),
         };
-        assert_eq!(expected, round.get_round_score());
+        round.play_face_up_cards();
+        assert_eq!(expected, round.get_round_score().unwrap());
     }
 
 }
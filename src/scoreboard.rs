@@ -1,11 +1,78 @@
 use super::Team;
-use std::ops::Add;
+use Error;
+use Result;
+
+// Points scored by a team
+pub type Points = u16;
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum Section {
+    Rey,
+    Flor,
+    Secansa,
+    Ali,
+    Truc,
+}
+
+// Sections in the order they are usually counted
+pub const SECTIONS: [Section; 5] = [
+    Section::Rey,
+    Section::Flor,
+    Section::Secansa,
+    Section::Ali,
+    Section::Truc,
+];
+
+impl Section {
+    pub fn name(self) -> &'static str {
+        match self {
+            Section::Rey => "rey",
+            Section::Flor => "flor",
+            Section::Secansa => "secansa",
+            Section::Ali => "ali",
+            Section::Truc => "truc",
+        }
+    }
+}
+
+// What happens to the rest of a round once one of its sections closes the cama
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum Overshoot {
+    // Every section is scored
+    ScoreAll,
+    // Sections after the deciding one are not scored
+    StopAtDecidingSection,
+    // Like StopAtDecidingSection, and the deciding section only scores up to the cama win score
+    DropSurplus,
+}
 
 #[derive(Debug, Copy, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
 pub struct GameConfig {
     pub game_win_score: u8,
     pub coto_win_score: u8,
-    pub cama_win_score: u8,
+    pub cama_win_score: Points,
+    pub overshoot: Overshoot,
+    // Order sections are counted in to find which one closes a cama
+    pub section_order: [Section; 5],
+}
+
+impl GameConfig {
+    pub fn sections(&self) -> Vec<Section> {
+        //! Sections in the order they are counted. Sections missing from `section_order` go last,
+        //! repeated ones count once.
+
+        let mut sections = Vec::new();
+        for &section in self.section_order.iter().chain(SECTIONS.iter()) {
+            if !sections.contains(&section) {
+                sections.push(section);
+            }
+        }
+        sections
+    }
 }
 
 impl Default for GameConfig {
@@ -14,37 +81,156 @@ impl Default for GameConfig {
             game_win_score: 2,
             coto_win_score: 2,
             cama_win_score: 40,
+            overshoot: Overshoot::ScoreAll,
+            section_order: SECTIONS,
         }
     }
 }
 
+// Progress made on the scoreboard by annotating a round
+#[derive(Debug, PartialEq, Copy, Clone)]
+pub enum ScoreboardEvent {
+    // Positions are zero based: the cama within its coto, the coto within the game
+    CamaWon {
+        coto: usize,
+        cama: usize,
+        winner: Team,
+        score: CamaScore,
+        // The section that took the winner to the cama win score
+        section: Section,
+    },
+    // Camas won by (Team1, Team2) in the coto
+    CotoWon {
+        coto: usize,
+        winner: Team,
+        camas: (u8, u8),
+    },
+    // Cotos won by (Team1, Team2) in the game
+    GameWon {
+        winner: Team,
+        cotos: (u8, u8),
+    },
+}
+
 #[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct Scoreboard {
     cotos: Vec<Coto>,
     pub game_config: GameConfig,
 }
 
 impl Scoreboard {
-    pub fn annotate(&mut self, round_score: RoundScore) {
-        //! Annotate a round on the scoreboard and perform management tasks to rotate camas and cotos when required
+    pub fn annotate(&mut self, round_score: RoundScore) -> Result<Vec<ScoreboardEvent>> {
+        //! Annotate a round on the scoreboard and perform management tasks to rotate camas and cotos when required.
+        //! Returns the camas, coto and game closed by the round, in that order.
+        //! The round is kept as given, the overshoot policy is applied whenever the cama is scored.
+        //! Rounds that would overflow the cama's score are rejected without annotating anything,
+        //! and so is any round once the game has a winner.
+
+        if self.winner()?.is_some() {
+            return Err(Error::GameFinished);
+        }
 
         let game_config = self.game_config;
-        self.get_current_coto_mut()
-            .annotate(round_score, game_config);
-        if self.get_current_coto().winner(self.game_config).is_some() {
+        let cama_index = self.get_current_coto()?.cames.len() - 1;
+        let coto_index = self.cotos.len() - 1;
+        let mut events = Vec::new();
+
+        self.get_current_coto_mut()?
+            .annotate(round_score, game_config)?;
+
+        let coto = self.get_current_coto()?;
+        let cama = &coto.cames[cama_index];
+        if let Some((winner, section)) = cama.deciding_section(game_config)? {
+            events.push(ScoreboardEvent::CamaWon {
+                coto: coto_index,
+                cama: cama_index,
+                winner,
+                score: cama.score(game_config)?,
+                section,
+            });
+        }
+        if let Some(winner) = coto.winner(game_config)? {
+            let cama_winners = coto
+                .cames
+                .iter()
+                .map(|cama| cama.winner(game_config))
+                .collect::<Result<Vec<_>>>()?;
+            events.push(ScoreboardEvent::CotoWon {
+                coto: coto_index,
+                winner,
+                camas: count_wins(cama_winners.into_iter()),
+            });
             self.start_coto();
         }
+        if let Some(winner) = self.winner()? {
+            events.push(ScoreboardEvent::GameWon {
+                winner,
+                cotos: count_wins(self.coto_results()?.into_iter()),
+            });
+        }
+        Ok(events)
     }
 
-    pub fn current_cama_score(&self) -> CamaScore {
-        self.get_current_coto().get_current_cama().score()
-    }
+    pub fn rounds(&self) -> Vec<RoundScore> {
+        //! Every annotated round, in order, as annotated before applying the overshoot policy
 
-    pub fn winner(&self, game_config: GameConfig) -> Option<Team> {
-        let winning_score = game_config.game_win_score;
         self.cotos
             .iter()
-            .map(|coto| coto.winner(game_config))
+            .flat_map(|coto| coto.cames.iter())
+            .flat_map(|cama| cama.rounds.iter().cloned())
+            .collect()
+    }
+
+    pub fn undo_last_round(&mut self) -> Result<RoundScore> {
+        //! Remove the last annotated round, reopening the cama or coto it closed, if any
+
+        let mut rounds = self.rounds();
+        let last = rounds.pop().ok_or(Error::RoundNotFound)?;
+        self.replay(rounds)?;
+        Ok(last)
+    }
+
+    pub fn amend_round(&mut self, index: usize, round_score: RoundScore) -> Result<RoundScore> {
+        //! Replace the round at `index` (as in `rounds`) and return the old score.
+        //! Camas and cotos are rotated again from scratch, as the new score may close them at a different round.
+
+        let mut rounds = self.rounds();
+        let old = *rounds.get(index).ok_or(Error::RoundNotFound)?;
+        rounds[index] = round_score;
+        self.replay(rounds)?;
+        Ok(old)
+    }
+
+    pub fn current_cama_score(&self) -> Result<CamaScore> {
+        self.get_current_coto()?
+            .get_current_cama()?
+            .score(self.game_config)
+    }
+
+    pub fn cama_winners(&self) -> Result<Vec<Team>> {
+        //! Winners of every finished cama, in order
+
+        let winners = self
+            .cotos
+            .iter()
+            .flat_map(|coto| coto.cames.iter())
+            .map(|cama| cama.winner(self.game_config))
+            .collect::<Result<Vec<_>>>()?;
+        Ok(winners.into_iter().flatten().collect())
+    }
+
+    pub fn coto_winners(&self) -> Result<Vec<Team>> {
+        //! Winners of every finished coto, in order
+
+        Ok(self.coto_results()?.into_iter().flatten().collect())
+    }
+
+    pub fn winner(&self) -> Result<Option<Team>> {
+        let winning_score = self.game_config.game_win_score;
+        let winner = self
+            .coto_results()?
+            .into_iter()
             .scan((0, 0), |state, x| {
                 *state = match x {
                     Some(Team::Team1) => (state.0 + 1, state.1),
@@ -61,26 +247,59 @@ impl Scoreboard {
                     Team::Team2
                 }
             })
-            .nth(0)
+            .next();
+        Ok(winner)
+    }
+
+    fn coto_results(&self) -> Result<Vec<Option<Team>>> {
+        //! Winner of every coto, None for the one in play
+
+        self.cotos
+            .iter()
+            .map(|coto| coto.winner(self.game_config))
+            .collect()
+    }
+
+    fn replay(&mut self, rounds: Vec<RoundScore>) -> Result<()> {
+        //! Annotate the rounds on a blank scoreboard. If any of them fails, this one is left untouched.
+
+        let mut scoreboard = Scoreboard {
+            cotos: Vec::new(),
+            game_config: self.game_config,
+        };
+        scoreboard.start_coto();
+        for round_score in rounds {
+            scoreboard.annotate(round_score)?;
+        }
+        self.cotos = scoreboard.cotos;
+        Ok(())
+    }
+
+    pub(crate) fn cotos(&self) -> &[Coto] {
+        &self.cotos
     }
 
     fn start_coto(&mut self) {
         self.cotos.push(Coto::new());
     }
 
-    fn get_current_coto(&self) -> &Coto {
-        self.cotos
-            .last()
-            .expect("Scoreboard not properly initialised")
+    fn get_current_coto(&self) -> Result<&Coto> {
+        self.cotos.last().ok_or(Error::CorruptScoreboard)
     }
 
-    fn get_current_coto_mut(&mut self) -> &mut Coto {
-        self.cotos
-            .last_mut()
-            .expect("Scoreboard not properly initialised")
+    fn get_current_coto_mut(&mut self) -> Result<&mut Coto> {
+        self.cotos.last_mut().ok_or(Error::CorruptScoreboard)
     }
 }
 
+fn count_wins<I: Iterator<Item = Option<Team>>>(winners: I) -> (u8, u8) {
+    winners.fold((0, 0), |wins, winner| match winner {
+        Some(Team::Team1) => (wins.0 + 1, wins.1),
+        Some(Team::Team2) => (wins.0, wins.1 + 1),
+        None => wins,
+    })
+}
+
 impl Default for Scoreboard {
     fn default() -> Self {
         let mut scoreboard = Scoreboard {
@@ -93,7 +312,8 @@ impl Default for Scoreboard {
 }
 
 #[derive(Debug, PartialEq, Copy, Clone)]
-pub struct RoundScoreSection(pub Team, pub u8);
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct RoundScoreSection(pub Team, pub Points);
 
 impl RoundScoreSection {
     fn to_score_delta(self) -> ScoreDelta {
@@ -112,21 +332,12 @@ impl RoundScoreSection {
 
 #[derive(Debug, PartialEq)]
 struct ScoreDelta {
-    team1: u8,
-    team2: u8,
-}
-
-impl Add<ScoreDelta> for CamaScore {
-    type Output = CamaScore;
-    fn add(self, rhs: ScoreDelta) -> Self::Output {
-        CamaScore {
-            team1: self.team1 + rhs.team1,
-            team2: self.team2 + rhs.team2,
-        }
-    }
+    team1: Points,
+    team2: Points,
 }
 
 #[derive(Debug, PartialEq, Copy, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct RoundScore {
     pub rey: Option<RoundScoreSection>,
     pub flor: Option<RoundScoreSection>,
@@ -136,7 +347,63 @@ pub struct RoundScore {
 }
 
 impl RoundScore {
-    fn to_score_deltas(&self) -> Vec<ScoreDelta> {
+    pub fn section(self, section: Section) -> Option<RoundScoreSection> {
+        match section {
+            Section::Rey => self.rey,
+            Section::Flor => self.flor,
+            Section::Secansa => self.secansa,
+            Section::Ali => self.ali,
+            Section::Truc => self.truc,
+        }
+    }
+
+    fn set_section(&mut self, section: Section, score: Option<RoundScoreSection>) {
+        match section {
+            Section::Rey => self.rey = score,
+            Section::Flor => self.flor = score,
+            Section::Secansa => self.secansa = score,
+            Section::Ali => self.ali = score,
+            Section::Truc => self.truc = score,
+        }
+    }
+
+    fn sections(self, order: &[Section]) -> Vec<(Section, RoundScoreSection)> {
+        //! Scored sections, in the given order
+
+        order
+            .iter()
+            .filter_map(|&section| self.section(section).map(|score| (section, score)))
+            .collect()
+    }
+
+    fn apply_overshoot(self, cama_score: CamaScore, game_config: GameConfig) -> Result<RoundScore> {
+        //! The part of the round that is scored on a cama with the given score, as the overshoot policy says
+
+        let winning_score = game_config.cama_win_score;
+        let mut scored = self;
+        let mut cama_score = cama_score;
+        let mut closed = false;
+        for (section, RoundScoreSection(team, points)) in self.sections(&game_config.sections()) {
+            if closed {
+                scored.set_section(section, None);
+                continue;
+            }
+            let points = match game_config.overshoot {
+                Overshoot::DropSurplus => {
+                    points.min(winning_score.saturating_sub(cama_score.team(team)))
+                }
+                _ => points,
+            };
+            let section_score = RoundScoreSection(team, points);
+            scored.set_section(section, Some(section_score));
+            cama_score = cama_score.checked_add(section_score.to_score_delta())?;
+            closed =
+                game_config.overshoot != Overshoot::ScoreAll && cama_score.max() >= winning_score;
+        }
+        Ok(scored)
+    }
+
+    fn to_score_deltas(self) -> Vec<ScoreDelta> {
         let deltas = [self.rey, self.flor, self.secansa, self.ali, self.truc];
         deltas
             .iter()
@@ -147,63 +414,108 @@ impl RoundScore {
 }
 
 #[derive(Debug, PartialEq, Default, Copy, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct CamaScore {
-    team1: u8,
-    team2: u8,
+    team1: Points,
+    team2: Points,
 }
 
 impl CamaScore {
-    pub fn max(self) -> u8 {
+    pub fn team(self, team: Team) -> Points {
+        match team {
+            Team::Team1 => self.team1,
+            Team::Team2 => self.team2,
+        }
+    }
+
+    pub fn max(self) -> Points {
         if self.team1 >= self.team2 {
             self.team1
         } else {
             self.team2
         }
     }
+
+    fn checked_add(self, delta: ScoreDelta) -> Result<CamaScore> {
+        match (
+            self.team1.checked_add(delta.team1),
+            self.team2.checked_add(delta.team2),
+        ) {
+            (Some(team1), Some(team2)) => Ok(CamaScore { team1, team2 }),
+            _ => Err(Error::ScoreOverflow),
+        }
+    }
 }
 
 #[derive(Debug, Default, PartialEq, Clone)]
-struct Cama {
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub(crate) struct Cama {
     rounds: Vec<RoundScore>,
 }
 
 impl Cama {
-    fn score(&self) -> CamaScore {
-        self.rounds
+    pub(crate) fn rounds(&self) -> &[RoundScore] {
+        &self.rounds
+    }
+
+    pub(crate) fn scored_rounds(&self, game_config: GameConfig) -> Result<Vec<RoundScore>> {
+        //! The rounds as scored by the overshoot policy
+
+        let mut cama_score = CamaScore::default();
+        let mut scored = Vec::with_capacity(self.rounds.len());
+        for round in &self.rounds {
+            let round = round.apply_overshoot(cama_score, game_config)?;
+            cama_score = round
+                .to_score_deltas()
+                .into_iter()
+                .try_fold(cama_score, CamaScore::checked_add)?;
+            scored.push(round);
+        }
+        Ok(scored)
+    }
+
+    fn score(&self, game_config: GameConfig) -> Result<CamaScore> {
+        self.scored_rounds(game_config)?
             .iter()
             .flat_map(|x| x.to_score_deltas())
-            .fold(CamaScore::default(), |acc, delta| acc + delta)
+            .try_fold(CamaScore::default(), CamaScore::checked_add)
     }
 
     fn annotate(&mut self, score: RoundScore) {
         self.rounds.push(score);
     }
 
-    fn winner(&self, game_config: GameConfig) -> Option<Team> {
+    fn winner(&self, game_config: GameConfig) -> Result<Option<Team>> {
+        Ok(self.deciding_section(game_config)?.map(|(team, _)| team))
+    }
+
+    pub(crate) fn deciding_section(
+        &self,
+        game_config: GameConfig,
+    ) -> Result<Option<(Team, Section)>> {
+        //! The winner of the cama and the section that took them to the cama win score
+
         let winning_score = game_config.cama_win_score;
-        self.rounds
-            .iter()
-            .flat_map(|x| x.to_score_deltas())
-            .scan(CamaScore::default(), |state, x| {
-                *state = *state + x;
-                Some(*state)
-            })
-            .filter(|cama_score| {
-                cama_score.team1 >= winning_score || cama_score.team2 >= winning_score
-            })
-            .map(|cama_score| {
+        let order = game_config.sections();
+        let mut cama_score = CamaScore::default();
+        for round in self.scored_rounds(game_config)? {
+            for (section, score) in round.sections(&order) {
+                cama_score = cama_score.checked_add(score.to_score_delta())?;
                 if cama_score.team1 >= winning_score {
-                    Team::Team1
-                } else {
-                    Team::Team2
+                    return Ok(Some((Team::Team1, section)));
                 }
-            })
-            .nth(0)
+                if cama_score.team2 >= winning_score {
+                    return Ok(Some((Team::Team2, section)));
+                }
+            }
+        }
+        Ok(None)
     }
 }
 
 #[derive(Debug, PartialEq, Clone)]
-struct Coto {
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub(crate) struct Coto {
     cames: Vec<Cama>,
 }
 
@@ -214,34 +526,50 @@ impl Coto {
         coto
     }
 
+    pub(crate) fn cames(&self) -> &[Cama] {
+        &self.cames
+    }
+
     fn start_cama(&mut self) {
         self.cames.push(Cama::default());
     }
 
-    fn get_current_cama(&self) -> &Cama {
-        self.cames.last().expect("Coto not properly initialised")
+    fn get_current_cama(&self) -> Result<&Cama> {
+        self.cames.last().ok_or(Error::CorruptScoreboard)
     }
 
-    fn get_current_cama_mut(&mut self) -> &mut Cama {
-        self.cames
-            .last_mut()
-            .expect("Coto not properly initialised")
+    fn get_current_cama_mut(&mut self) -> Result<&mut Cama> {
+        self.cames.last_mut().ok_or(Error::CorruptScoreboard)
     }
 
-    fn annotate(&mut self, round_score: RoundScore, game_config: GameConfig) {
+    fn annotate(&mut self, round_score: RoundScore, game_config: GameConfig) -> Result<()> {
         //! Annotate a round on the coto and perform management tasks to rotate camas when required
 
-        self.get_current_cama_mut().annotate(round_score);
-        if self.get_current_cama().winner(game_config).is_some() {
+        let cama = self.get_current_cama_mut()?;
+        cama.annotate(round_score);
+        // A round that overflows the cama's score is taken back
+        let winner = match cama.winner(game_config) {
+            Ok(winner) => winner,
+            Err(error) => {
+                cama.rounds.pop();
+                return Err(error);
+            }
+        };
+        if winner.is_some() {
             self.start_cama();
         }
+        Ok(())
     }
 
-    fn winner(&self, game_config: GameConfig) -> Option<Team> {
+    pub(crate) fn winner(&self, game_config: GameConfig) -> Result<Option<Team>> {
         let winning_score = game_config.coto_win_score;
-        self.cames
+        let cama_winners = self
+            .cames
             .iter()
             .map(|cama| cama.winner(game_config))
+            .collect::<Result<Vec<_>>>()?;
+        let winner = cama_winners
+            .into_iter()
             .scan((0, 0), |state, x| {
                 *state = match x {
                     Some(Team::Team1) => (state.0 + 1, state.1),
@@ -258,7 +586,8 @@ impl Coto {
                     Team::Team2
                 }
             })
-            .nth(0)
+            .next();
+        Ok(winner)
     }
 }
 
@@ -269,10 +598,10 @@ mod tests {
     #[test]
     fn scoreboard_get_current_coto() {
         let mut scoreboard: Scoreboard = Default::default();
-        scoreboard.get_current_coto_mut().start_cama();
-        let coto1 = scoreboard.get_current_coto().clone();
+        scoreboard.get_current_coto_mut().unwrap().start_cama();
+        let coto1 = scoreboard.get_current_coto().unwrap().clone();
         scoreboard.start_coto();
-        let coto2 = scoreboard.get_current_coto();
+        let coto2 = scoreboard.get_current_coto().unwrap();
         assert!(coto1 != *coto2);
     }
 
@@ -286,6 +615,64 @@ mod tests {
         assert!(!scoreboard.cotos.is_empty());
     }
 
+    #[test]
+    fn scoreboard_corrupt() {
+        let mut scoreboard = Scoreboard {
+            cotos: Vec::new(),
+            game_config: GameConfig::default(),
+        };
+        assert_eq!(
+            scoreboard.current_cama_score(),
+            Err(Error::CorruptScoreboard)
+        );
+        let result = scoreboard.annotate(RoundScore {
+            rey: None,
+            flor: None,
+            secansa: None,
+            ali: None,
+            truc: None,
+        });
+        assert_eq!(result, Err(Error::CorruptScoreboard));
+    }
+
+    #[test]
+    #[cfg(feature = "serde")]
+    fn scoreboard_serde() {
+        use serde_json;
+
+        let mut scoreboard: Scoreboard = Default::default();
+        scoreboard.game_config.cama_win_score = 5;
+        for &points in &[3, 4, 2] {
+            scoreboard
+                .annotate(RoundScore {
+                    rey: Some(RoundScoreSection(Team::Team2, 1)),
+                    flor: None,
+                    secansa: None,
+                    ali: None,
+                    truc: Some(RoundScoreSection(Team::Team1, points)),
+                })
+                .unwrap();
+        }
+
+        let json = serde_json::to_string(&scoreboard).unwrap();
+        let restored: Scoreboard = serde_json::from_str(&json).unwrap();
+        assert_eq!(restored.cotos, scoreboard.cotos);
+        assert_eq!(restored.game_config.cama_win_score, 5);
+
+        // Configs saved before the overshoot policy existed use the defaults
+        let config: GameConfig =
+            serde_json::from_str(r#"{"game_win_score":2,"coto_win_score":2,"cama_win_score":30}"#)
+                .unwrap();
+        assert_eq!(config.cama_win_score, 30);
+        assert_eq!(config.overshoot, Overshoot::ScoreAll);
+        assert_eq!(config.section_order, SECTIONS);
+        assert_eq!(restored.cama_winners(), scoreboard.cama_winners());
+        assert_eq!(
+            restored.current_cama_score(),
+            scoreboard.current_cama_score()
+        );
+    }
+
     #[test]
     fn scoreboard_new() {
         let scoreboard: Scoreboard = Default::default();
@@ -296,42 +683,48 @@ mod tests {
     #[test]
     fn scoreboard_annotate_writes_round_score() {
         let mut scoreboard: Scoreboard = Default::default();
-        scoreboard.annotate(RoundScore {
-            rey: None,
-            flor: None,
-            secansa: None,
-            ali: None,
-            truc: None,
-        });
-        assert!(
-            !scoreboard
-                .get_current_coto()
-                .get_current_cama()
-                .rounds
-                .is_empty()
-        );
+        scoreboard
+            .annotate(RoundScore {
+                rey: None,
+                flor: None,
+                secansa: None,
+                ali: None,
+                truc: None,
+            })
+            .unwrap();
+        assert!(!scoreboard
+            .get_current_coto()
+            .unwrap()
+            .get_current_cama()
+            .unwrap()
+            .rounds
+            .is_empty());
     }
 
     #[test]
     fn scoreboard_annotate_rotates_camas() {
         let mut scoreboard: Scoreboard = Default::default();
         let max_cama_score = scoreboard.game_config.cama_win_score;
-        scoreboard.annotate(RoundScore {
-            rey: None,
-            flor: None,
-            secansa: None,
-            ali: None,
-            truc: None,
-        });
-        assert_eq!(scoreboard.get_current_coto().cames.len(), 1);
-        scoreboard.annotate(RoundScore {
-            rey: None,
-            flor: None,
-            secansa: None,
-            ali: None,
-            truc: Some(RoundScoreSection(Team::Team1, max_cama_score)),
-        });
-        assert_eq!(scoreboard.get_current_coto().cames.len(), 2);
+        scoreboard
+            .annotate(RoundScore {
+                rey: None,
+                flor: None,
+                secansa: None,
+                ali: None,
+                truc: None,
+            })
+            .unwrap();
+        assert_eq!(scoreboard.get_current_coto().unwrap().cames.len(), 1);
+        scoreboard
+            .annotate(RoundScore {
+                rey: None,
+                flor: None,
+                secansa: None,
+                ali: None,
+                truc: Some(RoundScoreSection(Team::Team1, max_cama_score)),
+            })
+            .unwrap();
+        assert_eq!(scoreboard.get_current_coto().unwrap().cames.len(), 2);
     }
 
     #[test]
@@ -339,13 +732,15 @@ mod tests {
         let mut scoreboard: Scoreboard = Default::default();
         let max_coto_score = scoreboard.game_config.coto_win_score;
         for _ in 0..max_coto_score {
-            scoreboard.annotate(RoundScore {
-                rey: None,
-                flor: None,
-                secansa: None,
-                ali: None,
-                truc: Some(RoundScoreSection(Team::Team1, 40)),
-            });
+            scoreboard
+                .annotate(RoundScore {
+                    rey: None,
+                    flor: None,
+                    secansa: None,
+                    ali: None,
+                    truc: Some(RoundScoreSection(Team::Team1, 40)),
+                })
+                .unwrap();
         }
         assert_eq!(scoreboard.cotos.len(), 2);
     }
@@ -358,16 +753,19 @@ mod tests {
                 cama_win_score: 40,
                 coto_win_score: 2,
                 game_win_score: 2,
+                ..GameConfig::default()
             },
         };
         scoreboard.start_coto();
-        assert!(scoreboard.winner(scoreboard.game_config).is_none());
+        assert!(scoreboard.winner().unwrap().is_none());
 
         fn annotate(scoreboard: &mut Scoreboard, team: Team) {
             let cama_win_score = scoreboard.game_config.cama_win_score;
             scoreboard
                 .get_current_coto_mut()
+                .unwrap()
                 .get_current_cama_mut()
+                .unwrap()
                 .annotate(RoundScore {
                     rey: None,
                     flor: None,
@@ -375,39 +773,338 @@ mod tests {
                     ali: None,
                     truc: Some(RoundScoreSection(team, cama_win_score)),
                 });
-            scoreboard.get_current_coto_mut().start_cama();
+            scoreboard.get_current_coto_mut().unwrap().start_cama();
         }
 
         annotate(&mut scoreboard, Team::Team1);
-        assert!(scoreboard.winner(scoreboard.game_config).is_none());
+        assert!(scoreboard.winner().unwrap().is_none());
 
         annotate(&mut scoreboard, Team::Team2);
-        assert!(scoreboard.winner(scoreboard.game_config).is_none());
+        assert!(scoreboard.winner().unwrap().is_none());
 
         annotate(&mut scoreboard, Team::Team2);
         // Team2 coto
-        assert!(scoreboard.winner(scoreboard.game_config).is_none());
+        assert!(scoreboard.winner().unwrap().is_none());
 
         scoreboard.start_coto();
 
         annotate(&mut scoreboard, Team::Team2);
-        assert!(scoreboard.winner(scoreboard.game_config).is_none());
+        assert!(scoreboard.winner().unwrap().is_none());
 
         annotate(&mut scoreboard, Team::Team1);
-        assert!(scoreboard.winner(scoreboard.game_config).is_none());
+        assert!(scoreboard.winner().unwrap().is_none());
 
         annotate(&mut scoreboard, Team::Team1);
         // Team1 coto
-        assert!(scoreboard.winner(scoreboard.game_config).is_none());
+        assert!(scoreboard.winner().unwrap().is_none());
 
         scoreboard.start_coto();
 
         annotate(&mut scoreboard, Team::Team1);
-        assert!(scoreboard.winner(scoreboard.game_config).is_none());
+        assert!(scoreboard.winner().unwrap().is_none());
 
         annotate(&mut scoreboard, Team::Team1);
         // Team1 wins!
-        assert_eq!(scoreboard.winner(scoreboard.game_config), Some(Team::Team1));
+        assert_eq!(scoreboard.winner(), Ok(Some(Team::Team1)));
+    }
+
+    fn truc(team: Team, points: Points) -> RoundScore {
+        RoundScore {
+            rey: None,
+            flor: None,
+            secansa: None,
+            ali: None,
+            truc: Some(RoundScoreSection(team, points)),
+        }
+    }
+
+    #[test]
+    fn scoreboard_undo_last_round() {
+        let mut scoreboard: Scoreboard = Default::default();
+        assert_eq!(scoreboard.undo_last_round(), Err(Error::RoundNotFound));
+
+        scoreboard.annotate(truc(Team::Team1, 10)).unwrap();
+        scoreboard.annotate(truc(Team::Team1, 30)).unwrap();
+        assert_eq!(scoreboard.cama_winners(), Ok(vec![Team::Team1]));
+
+        // Undoing the round that closed the cama reopens it
+        assert_eq!(scoreboard.undo_last_round(), Ok(truc(Team::Team1, 30)));
+        assert!(scoreboard.cama_winners().unwrap().is_empty());
+        assert_eq!(scoreboard.get_current_coto().unwrap().cames.len(), 1);
+        assert_eq!(scoreboard.current_cama_score().unwrap().max(), 10);
+
+        // And the same for cotos
+        scoreboard.annotate(truc(Team::Team1, 30)).unwrap();
+        scoreboard.annotate(truc(Team::Team1, 40)).unwrap();
+        assert_eq!(scoreboard.coto_winners(), Ok(vec![Team::Team1]));
+        assert_eq!(scoreboard.cotos.len(), 2);
+        scoreboard.undo_last_round().unwrap();
+        assert!(scoreboard.coto_winners().unwrap().is_empty());
+        assert_eq!(scoreboard.cotos.len(), 1);
+        assert_eq!(scoreboard.rounds().len(), 2);
+    }
+
+    #[test]
+    fn scoreboard_amend_round() {
+        let mut scoreboard: Scoreboard = Default::default();
+        scoreboard.annotate(truc(Team::Team1, 10)).unwrap();
+        scoreboard.annotate(truc(Team::Team2, 5)).unwrap();
+        scoreboard.annotate(truc(Team::Team1, 20)).unwrap();
+        assert_eq!(
+            scoreboard.amend_round(3, truc(Team::Team1, 1)),
+            Err(Error::RoundNotFound)
+        );
+
+        // The first round closes the cama now, so the other two move to a new one
+        assert_eq!(
+            scoreboard.amend_round(0, truc(Team::Team1, 40)),
+            Ok(truc(Team::Team1, 10))
+        );
+        assert_eq!(scoreboard.cama_winners(), Ok(vec![Team::Team1]));
+        let coto = scoreboard.get_current_coto().unwrap();
+        assert_eq!(coto.cames.len(), 2);
+        assert_eq!(coto.cames[0].rounds, vec![truc(Team::Team1, 40)]);
+        assert_eq!(coto.cames[1].rounds.len(), 2);
+
+        // And back
+        scoreboard.amend_round(0, truc(Team::Team1, 10)).unwrap();
+        assert!(scoreboard.cama_winners().unwrap().is_empty());
+        assert_eq!(scoreboard.get_current_coto().unwrap().cames.len(), 1);
+        assert_eq!(scoreboard.current_cama_score().unwrap().max(), 30);
+        assert_eq!(scoreboard.rounds()[1], truc(Team::Team2, 5));
+    }
+
+    #[test]
+    fn scoreboard_amend_overshoot() {
+        // Team1's second round closes the cama with 5 of its 20 points
+        let mut scoreboard: Scoreboard = Default::default();
+        scoreboard.game_config.overshoot = Overshoot::DropSurplus;
+        scoreboard.annotate(truc(Team::Team1, 35)).unwrap();
+        scoreboard.annotate(truc(Team::Team1, 20)).unwrap();
+        assert_eq!(scoreboard.cama_winners(), Ok(vec![Team::Team1]));
+
+        // Amending the first round scores the second one whole again
+        scoreboard.amend_round(0, truc(Team::Team1, 5)).unwrap();
+        assert!(scoreboard.cama_winners().unwrap().is_empty());
+        assert_eq!(
+            scoreboard.current_cama_score(),
+            Ok(CamaScore {
+                team1: 25,
+                team2: 0
+            })
+        );
+        assert_eq!(scoreboard.undo_last_round(), Ok(truc(Team::Team1, 20)));
+    }
+
+    #[test]
+    fn scoreboard_annotate_events() {
+        let mut scoreboard: Scoreboard = Default::default();
+        assert_eq!(scoreboard.annotate(truc(Team::Team1, 30)), Ok(vec![]));
+        assert_eq!(
+            scoreboard.annotate(truc(Team::Team1, 10)),
+            Ok(vec![ScoreboardEvent::CamaWon {
+                coto: 0,
+                cama: 0,
+                winner: Team::Team1,
+                score: CamaScore {
+                    team1: 40,
+                    team2: 0
+                },
+                section: Section::Truc,
+            }])
+        );
+        scoreboard.annotate(truc(Team::Team2, 40)).unwrap();
+        assert_eq!(
+            scoreboard.annotate(truc(Team::Team1, 45)).unwrap()[1..],
+            [ScoreboardEvent::CotoWon {
+                coto: 0,
+                winner: Team::Team1,
+                camas: (2, 1),
+            }]
+        );
+
+        scoreboard.annotate(truc(Team::Team1, 40)).unwrap();
+        let events = scoreboard.annotate(truc(Team::Team1, 40)).unwrap();
+        assert_eq!(events.len(), 3);
+        assert_eq!(
+            events[0],
+            ScoreboardEvent::CamaWon {
+                coto: 1,
+                cama: 1,
+                winner: Team::Team1,
+                score: CamaScore {
+                    team1: 40,
+                    team2: 0
+                },
+                section: Section::Truc,
+            }
+        );
+        assert_eq!(
+            events[2],
+            ScoreboardEvent::GameWon {
+                winner: Team::Team1,
+                cotos: (2, 0),
+            }
+        );
+        assert_eq!(scoreboard.winner(), Ok(Some(Team::Team1)));
+
+        // Nothing is annotated after the last coto
+        let rounds = scoreboard.rounds();
+        assert_eq!(
+            scoreboard.annotate(truc(Team::Team2, 1)),
+            Err(Error::GameFinished)
+        );
+        assert_eq!(scoreboard.rounds(), rounds);
+    }
+
+    fn overshoot_scoreboard(overshoot: Overshoot, team1: Points, team2: Points) -> Scoreboard {
+        let mut scoreboard: Scoreboard = Default::default();
+        scoreboard.game_config.overshoot = overshoot;
+        scoreboard.annotate(truc(Team::Team1, team1)).unwrap();
+        scoreboard.annotate(truc(Team::Team2, team2)).unwrap();
+        scoreboard
+    }
+
+    fn scored_rounds(scoreboard: &Scoreboard) -> Vec<RoundScore> {
+        scoreboard
+            .cotos
+            .iter()
+            .flat_map(|coto| coto.cames.iter())
+            .flat_map(|cama| cama.scored_rounds(scoreboard.game_config).unwrap())
+            .collect()
+    }
+
+    fn cama_won_section(events: &[ScoreboardEvent]) -> Option<Section> {
+        events.iter().find_map(|event| match *event {
+            ScoreboardEvent::CamaWon { section, .. } => Some(section),
+            _ => None,
+        })
+    }
+
+    #[test]
+    fn scoreboard_overshoot() {
+        let round = RoundScore {
+            rey: Some(RoundScoreSection(Team::Team1, 1)),
+            flor: Some(RoundScoreSection(Team::Team1, 6)),
+            secansa: None,
+            ali: Some(RoundScoreSection(Team::Team2, 5)),
+            truc: None,
+        };
+
+        let mut scoreboard = overshoot_scoreboard(Overshoot::ScoreAll, 36, 10);
+        let events = scoreboard.annotate(round).unwrap();
+        assert_eq!(cama_won_section(&events), Some(Section::Flor));
+        assert_eq!(scored_rounds(&scoreboard)[2], round);
+
+        // Team2's ali comes after the flor that closed the cama
+        let mut scoreboard = overshoot_scoreboard(Overshoot::StopAtDecidingSection, 36, 10);
+        let events = scoreboard.annotate(round).unwrap();
+        assert_eq!(cama_won_section(&events), Some(Section::Flor));
+        assert_eq!(
+            scored_rounds(&scoreboard)[2],
+            RoundScore { ali: None, ..round }
+        );
+        // The round itself is kept whole
+        assert_eq!(scoreboard.rounds()[2], round);
+
+        // And only 3 of the flor's points were needed
+        let mut scoreboard = overshoot_scoreboard(Overshoot::DropSurplus, 36, 10);
+        let events = scoreboard.annotate(round).unwrap();
+        assert_eq!(
+            events[0],
+            ScoreboardEvent::CamaWon {
+                coto: 0,
+                cama: 0,
+                winner: Team::Team1,
+                score: CamaScore {
+                    team1: 40,
+                    team2: 10
+                },
+                section: Section::Flor,
+            }
+        );
+        assert_eq!(
+            scored_rounds(&scoreboard)[2],
+            RoundScore {
+                flor: Some(RoundScoreSection(Team::Team1, 3)),
+                ali: None,
+                ..round
+            }
+        );
+
+        // Nothing changes for rounds that don't close the cama
+        let mut scoreboard = overshoot_scoreboard(Overshoot::DropSurplus, 10, 10);
+        assert_eq!(scoreboard.annotate(round), Ok(vec![]));
+        assert_eq!(scored_rounds(&scoreboard)[2], round);
+    }
+
+    #[test]
+    fn scoreboard_section_order() {
+        let round = RoundScore {
+            rey: Some(RoundScoreSection(Team::Team1, 2)),
+            flor: None,
+            secansa: None,
+            ali: None,
+            truc: Some(RoundScoreSection(Team::Team2, 1)),
+        };
+
+        // Both teams reach 40, the order decides who got there first
+        let mut scoreboard = overshoot_scoreboard(Overshoot::ScoreAll, 38, 39);
+        let events = scoreboard.annotate(round).unwrap();
+        assert_eq!(cama_won_section(&events), Some(Section::Rey));
+        assert_eq!(scoreboard.cama_winners(), Ok(vec![Team::Team1]));
+
+        let mut scoreboard = overshoot_scoreboard(Overshoot::StopAtDecidingSection, 38, 39);
+        scoreboard.game_config.section_order = [
+            Section::Truc,
+            Section::Rey,
+            Section::Flor,
+            Section::Secansa,
+            Section::Ali,
+        ];
+        let events = scoreboard.annotate(round).unwrap();
+        assert_eq!(cama_won_section(&events), Some(Section::Truc));
+        assert_eq!(scoreboard.cama_winners(), Ok(vec![Team::Team2]));
+        assert_eq!(
+            scored_rounds(&scoreboard)[2],
+            RoundScore { rey: None, ..round }
+        );
+
+        // Sections left out of the order still count, after the listed ones
+        assert_eq!(
+            round.sections(
+                &GameConfig {
+                    section_order: [Section::Truc; 5],
+                    ..GameConfig::default()
+                }
+                .sections()
+            ),
+            vec![
+                (Section::Truc, RoundScoreSection(Team::Team2, 1)),
+                (Section::Rey, RoundScoreSection(Team::Team1, 2)),
+            ]
+        );
+    }
+
+    #[test]
+    fn scoreboard_annotate_overflow() {
+        let mut scoreboard: Scoreboard = Default::default();
+        scoreboard.game_config.cama_win_score = Points::MAX;
+        scoreboard.annotate(truc(Team::Team1, 60000)).unwrap();
+        assert_eq!(
+            scoreboard.annotate(truc(Team::Team1, 6000)),
+            Err(Error::ScoreOverflow)
+        );
+        assert_eq!(scoreboard.rounds().len(), 1);
+        scoreboard.annotate(truc(Team::Team2, 6000)).unwrap();
+
+        // An amend that would overflow leaves the scoreboard as it was
+        assert_eq!(
+            scoreboard.amend_round(1, truc(Team::Team1, 6000)),
+            Err(Error::ScoreOverflow)
+        );
+        assert_eq!(scoreboard.rounds()[1], truc(Team::Team2, 6000));
+        assert_eq!(scoreboard.current_cama_score().unwrap().max(), 60000);
     }
 
     #[test]
@@ -427,16 +1124,16 @@ mod tests {
     #[test]
     fn coto_get_current_cama() {
         let mut coto = Coto::new();
-        coto.get_current_cama_mut().annotate(RoundScore {
+        coto.get_current_cama_mut().unwrap().annotate(RoundScore {
             rey: None,
             flor: Some(RoundScoreSection(Team::Team1, 3)),
             secansa: Some(RoundScoreSection(Team::Team1, 1)),
             ali: Some(RoundScoreSection(Team::Team2, 5)),
             truc: Some(RoundScoreSection(Team::Team1, 1)),
         });
-        let cama1 = coto.get_current_cama().clone();
+        let cama1 = coto.get_current_cama().unwrap().clone();
         coto.start_cama();
-        let cama2 = coto.get_current_cama();
+        let cama2 = coto.get_current_cama().unwrap();
         assert!(cama1 != *cama2);
     }
 
@@ -453,8 +1150,9 @@ mod tests {
                 truc: None,
             },
             game_config,
-        );
-        assert!(!coto.get_current_cama().rounds.is_empty());
+        )
+        .unwrap();
+        assert!(!coto.get_current_cama().unwrap().rounds.is_empty());
     }
 
     #[test]
@@ -470,7 +1168,8 @@ mod tests {
                 truc: Some(RoundScoreSection(Team::Team1, 3)),
             },
             game_config,
-        );
+        )
+        .unwrap();
         assert_eq!(coto.cames.len(), 1);
         coto.annotate(
             RoundScore {
@@ -481,7 +1180,8 @@ mod tests {
                 truc: Some(RoundScoreSection(Team::Team1, 37)),
             },
             game_config,
-        );
+        )
+        .unwrap();
         assert_eq!(coto.cames.len(), 2);
     }
 
@@ -489,30 +1189,30 @@ mod tests {
     fn coto_winner() {
         let mut coto = Coto::new();
         let game_config = GameConfig::default();
-        assert_eq!(coto.winner(game_config), None);
+        assert_eq!(coto.winner(game_config), Ok(None));
 
         coto.start_cama();
-        coto.get_current_cama_mut().annotate(RoundScore {
+        coto.get_current_cama_mut().unwrap().annotate(RoundScore {
             rey: None,
             flor: None,
             secansa: None,
             ali: None,
             truc: Some(RoundScoreSection(Team::Team1, 40)),
         });
-        assert_eq!(coto.winner(game_config), None);
+        assert_eq!(coto.winner(game_config), Ok(None));
 
         coto.start_cama();
-        coto.get_current_cama_mut().annotate(RoundScore {
+        coto.get_current_cama_mut().unwrap().annotate(RoundScore {
             rey: None,
             flor: None,
             secansa: None,
             ali: None,
             truc: Some(RoundScoreSection(Team::Team2, 40)),
         });
-        assert_eq!(coto.winner(game_config), None);
+        assert_eq!(coto.winner(game_config), Ok(None));
 
         coto.start_cama();
-        coto.get_current_cama_mut().annotate(RoundScore {
+        coto.get_current_cama_mut().unwrap().annotate(RoundScore {
             rey: None,
             flor: None,
             secansa: None,
@@ -520,7 +1220,7 @@ mod tests {
             truc: Some(RoundScoreSection(Team::Team1, 40)),
         });
 
-        assert_eq!(coto.winner(game_config), Some(Team::Team1));
+        assert_eq!(coto.winner(game_config), Ok(Some(Team::Team1)));
     }
 
     #[test]
@@ -556,7 +1256,7 @@ mod tests {
             ali: Some(RoundScoreSection(Team::Team1, 1)),
             truc: Some(RoundScoreSection(Team::Team1, 1)),
         });
-        let score = cama.score();
+        let score = cama.score(GameConfig::default()).unwrap();
         let expected = CamaScore {
             team1: 12,
             team2: 11,
@@ -580,7 +1280,7 @@ mod tests {
             rounds: vec![current_score],
         };
 
-        assert_eq!(current_cama.winner(game_config), None);
+        assert_eq!(current_cama.winner(game_config), Ok(None));
 
         // Team1: 5, Team2: 6
         current_cama.annotate(RoundScore {
@@ -593,6 +1293,15 @@ mod tests {
 
         let winner = current_cama.winner(game_config);
 
-        assert_eq!(winner, Some(Team::Team2));
+        assert_eq!(winner, Ok(Some(Team::Team2)));
+
+        // A cama whose score overflows isn't undecided
+        let cama = Cama {
+            rounds: vec![truc(Team::Team1, 40000), truc(Team::Team1, 40000)],
+        };
+        assert_eq!(
+            cama.deciding_section(game_config),
+            Err(Error::ScoreOverflow)
+        );
     }
 }
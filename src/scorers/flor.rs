@@ -1,10 +1,13 @@
 use super::Scorer;
 use hands::{flor, Hand};
 use scoreboard;
+use Error;
+use Result;
 use Round;
 use Team;
 
 #[derive(Debug, Copy, Clone, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub enum AgreedBet {
     // Announced and Envit can be agreed but we gotta check the cards to get the winner
     Announced(Option<Team>),
@@ -13,7 +16,7 @@ pub enum AgreedBet {
     Resto,
 }
 
-#[derive(Default)]
+#[derive(Default, Clone)]
 pub struct FlorScorer {
     agreed_bet: Option<AgreedBet>,
 }
@@ -25,16 +28,24 @@ impl FlorScorer {
 }
 
 impl Scorer for FlorScorer {
-    fn get_score(&self, round: &Round) -> Option<scoreboard::RoundScoreSection> {
+    fn get_score(&self, round: &Round) -> Result<Option<scoreboard::RoundScoreSection>> {
         // Game must've been announced to be scored
-        let game_bet = self.agreed_bet?;
+        let game_bet = match self.agreed_bet {
+            Some(game_bet) => game_bet,
+            None => return Ok(None),
+        };
 
         let bet_winner = match game_bet {
             AgreedBet::Announced(winner) | AgreedBet::Envit(winner) => winner,
             _ => None,
         };
 
-        let cards_winner = round.get_winner_from_cards::<flor::Flor>();
+        // The joc registered under the name, which house rules may have replaced
+        let joc = match round.game.jocs().get(flor::Flor::NAME) {
+            Some(joc) => joc,
+            None => return Ok(None),
+        };
+        let cards_winner = joc.winner(round);
 
         let winner = match (bet_winner, cards_winner) {
             // If there was a rejected bet, we've got a direct winner
@@ -42,42 +53,36 @@ impl Scorer for FlorScorer {
             // Otherwise get the winner from the facing up cards
             (None, Some(winner)) => winner,
             // If there still no winner, we can't score anything
-            _ => return None,
+            _ => return Ok(None),
         };
 
-        let winner_flor_count = round
-            .seats
-            .iter()
-            .enumerate()
-            .filter(|&(pos, seat)| seat.get_team(pos as u8) == winner)
-            .filter_map(|(_, seat)| flor::Flor::from_cards(&seat.face_up_cards, round.marker))
-            .count() as u8;
-
-        let total_flor_count = round
-            .seats
-            .iter()
-            .enumerate()
-            .filter_map(|(_, seat)| flor::Flor::from_cards(&seat.face_up_cards, round.marker))
-            .count() as u8;
-
-        // Compute resto
-        let max_score = round.game.scoreboard.current_cama_score().max();
-        let cama_win_score = round.game.scoreboard.game_config.cama_win_score;
-        let resto = cama_win_score - max_score;
+        let winner_flor_value = joc.team_score(round, winner)?;
+        let total_flor_value = winner_flor_value
+            .checked_add(joc.team_score(round, winner.opponent())?)
+            .ok_or(Error::ScoreOverflow)?;
+
+        // Compute resto. Nothing is left if a score already reached the limit.
+        let scoreboard = round.game.scoreboard();
+        let max_score = scoreboard.current_cama_score()?.max();
+        let cama_win_score = scoreboard.game_config.cama_win_score;
+        let resto = cama_win_score.saturating_sub(max_score);
 
         let score = match game_bet {
-            AgreedBet::Announced(_) => winner_flor_count * 3,
-            AgreedBet::Envit(_) => total_flor_count * 3,
-            AgreedBet::Resto => total_flor_count * 3 + resto,
+            AgreedBet::Announced(_) => winner_flor_value,
+            AgreedBet::Envit(_) => total_flor_value,
+            AgreedBet::Resto => total_flor_value
+                .checked_add(resto)
+                .ok_or(Error::ScoreOverflow)?,
         };
 
-        Some(scoreboard::RoundScoreSection(winner, score))
+        Ok(Some(scoreboard::RoundScoreSection(winner, score)))
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use cardset::CardSet;
     use deck;
     use Game;
     use Player;
@@ -97,14 +102,14 @@ mod tests {
         assert_eq!(flor_scorer.agreed_bet, Some(bet));
     }
 
-    fn flor_tests_round_fixture(game: &Game) -> Round {
-        let mut round = Round::new(game, &game.players[0], deck::Deck::default());
+    fn flor_tests_round_fixture(game: &Game) -> Round<'_> {
+        let mut round = Round::new(game, &game.players[0], deck::Deck::default()).unwrap();
 
         round.seats = vec![
             // 34
             Seat {
                 player: &game.players[0],
-                hand: vec![],
+                hand: CardSet::empty(),
                 face_up_cards: vec![
                     deck::Card {
                         suit: deck::Suit::Copas,
@@ -123,7 +128,7 @@ mod tests {
             // 35
             Seat {
                 player: &game.players[0],
-                hand: vec![],
+                hand: CardSet::empty(),
                 face_up_cards: vec![
                     deck::Card {
                         suit: deck::Suit::Oros,
@@ -142,7 +147,7 @@ mod tests {
             // 35
             Seat {
                 player: &game.players[0],
-                hand: vec![],
+                hand: CardSet::empty(),
                 face_up_cards: vec![
                     deck::Card {
                         suit: deck::Suit::Oros,
@@ -161,7 +166,7 @@ mod tests {
             // 34
             Seat {
                 player: &game.players[0],
-                hand: vec![],
+                hand: CardSet::empty(),
                 face_up_cards: vec![
                     deck::Card {
                         suit: deck::Suit::Copas,
@@ -188,7 +193,7 @@ mod tests {
         let round = flor_tests_round_fixture(&game);
         let flor_scorer = FlorScorer::default();
 
-        assert!(flor_scorer.get_score(&round).is_none())
+        assert!(flor_scorer.get_score(&round).unwrap().is_none())
     }
 
     #[test]
@@ -196,7 +201,7 @@ mod tests {
         // This situation should be impossible! Testing as it can be done in code anyway
 
         let game = Game::new(vec![Player::new("a"), Player::new("b")]);
-        let mut round = Round::new(&game, &game.players[0], deck::Deck::default());
+        let mut round = Round::new(&game, &game.players[0], deck::Deck::default()).unwrap();
 
         round.seats = vec![
             // No flor
@@ -216,14 +221,14 @@ mod tests {
                         value: deck::Value::Caballo,
                     },
                 ],
-                hand: vec![],
+                hand: CardSet::empty(),
             },
         ];
 
         let mut flor_scorer = FlorScorer::default();
         flor_scorer.set_bet(AgreedBet::Envit(None));
 
-        assert!(flor_scorer.get_score(&round).is_none())
+        assert!(flor_scorer.get_score(&round).unwrap().is_none())
     }
 
     #[test]
@@ -235,7 +240,7 @@ mod tests {
         flor_scorer.set_bet(AgreedBet::Announced(Some(Team::Team2)));
 
         let expected = Some(scoreboard::RoundScoreSection(Team::Team2, 6));
-        assert_eq!(flor_scorer.get_score(&round), expected);
+        assert_eq!(flor_scorer.get_score(&round).unwrap(), expected);
     }
 
     #[test]
@@ -247,7 +252,7 @@ mod tests {
         flor_scorer.set_bet(AgreedBet::Announced(None));
 
         let expected = Some(scoreboard::RoundScoreSection(Team::Team2, 6));
-        assert_eq!(flor_scorer.get_score(&round), expected);
+        assert_eq!(flor_scorer.get_score(&round).unwrap(), expected);
     }
 
     #[test]
@@ -259,7 +264,7 @@ mod tests {
         flor_scorer.set_bet(AgreedBet::Envit(Some(Team::Team2)));
 
         let expected = Some(scoreboard::RoundScoreSection(Team::Team2, 12));
-        assert_eq!(flor_scorer.get_score(&round), expected);
+        assert_eq!(flor_scorer.get_score(&round).unwrap(), expected);
     }
 
     #[test]
@@ -271,25 +276,50 @@ mod tests {
         flor_scorer.set_bet(AgreedBet::Envit(None));
 
         let expected = Some(scoreboard::RoundScoreSection(Team::Team2, 12));
-        assert_eq!(flor_scorer.get_score(&round), expected);
+        assert_eq!(flor_scorer.get_score(&round).unwrap(), expected);
     }
 
     #[test]
     fn get_flor_score_resto_won_from_cards() {
         let mut game = Game::new(vec![Player::new("a"), Player::new("b")]);
-        game.scoreboard.annotate(scoreboard::RoundScore {
-            rey: None,
-            flor: None,
-            secansa: None,
-            ali: None,
-            truc: Some(scoreboard::RoundScoreSection(Team::Team1, 25)),
-        });
+        game.scoreboard
+            .annotate(scoreboard::RoundScore {
+                rey: None,
+                flor: None,
+                secansa: None,
+                ali: None,
+                truc: Some(scoreboard::RoundScoreSection(Team::Team1, 25)),
+            })
+            .unwrap();
         let round = flor_tests_round_fixture(&game);
 
         let mut flor_scorer = FlorScorer::default();
         flor_scorer.set_bet(AgreedBet::Resto);
 
         let expected = Some(scoreboard::RoundScoreSection(Team::Team2, 27));
-        assert_eq!(flor_scorer.get_score(&round), expected);
+        assert_eq!(flor_scorer.get_score(&round).unwrap(), expected);
+    }
+
+    #[test]
+    fn get_flor_score_resto_past_limit() {
+        let mut game = Game::new(vec![Player::new("a"), Player::new("b")]);
+        game.scoreboard
+            .annotate(scoreboard::RoundScore {
+                rey: None,
+                flor: None,
+                secansa: None,
+                ali: None,
+                truc: Some(scoreboard::RoundScoreSection(Team::Team1, 25)),
+            })
+            .unwrap();
+        // The limit was lowered below the current score, so there's no resto left
+        game.scoreboard.game_config.cama_win_score = 20;
+        let round = flor_tests_round_fixture(&game);
+
+        let mut flor_scorer = FlorScorer::default();
+        flor_scorer.set_bet(AgreedBet::Resto);
+
+        let expected = Some(scoreboard::RoundScoreSection(Team::Team2, 12));
+        assert_eq!(flor_scorer.get_score(&round).unwrap(), expected);
     }
 }
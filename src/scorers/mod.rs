@@ -5,8 +5,9 @@ pub mod secansa;
 pub mod truc;
 
 use scoreboard;
+use Result;
 use Round;
 
 pub trait Scorer {
-    fn get_score(&self, round: &Round) -> Option<scoreboard::RoundScoreSection>;
+    fn get_score(&self, round: &Round) -> Result<Option<scoreboard::RoundScoreSection>>;
 }
@@ -1,38 +1,40 @@
 use super::Scorer;
 use deck;
 use scoreboard;
+use Result;
 use Round;
 
 #[derive(Default)]
 pub struct ReyScorer;
 
 impl Scorer for ReyScorer {
-    fn get_score(&self, round: &Round) -> Option<scoreboard::RoundScoreSection> {
-        let winner_team = round
-            .iter_from_hand()
-            .skip_while(|&(_team, seat)| {
-                !seat.face_up_cards
-                    .iter()
-                    .map(|card| card.value)
-                    .any(|value| value == deck::Value::Rey)
-            })
-            .nth(0)?
-            .0;
+    fn get_score(&self, round: &Round) -> Result<Option<scoreboard::RoundScoreSection>> {
+        let winner_team = round.iter_from_hand().find(|&(_team, seat)| {
+            seat.face_up_cards
+                .iter()
+                .map(|card| card.value)
+                .any(|value| value == deck::Value::Rey)
+        });
+        let winner_team = match winner_team {
+            Some((team, _seat)) => team,
+            None => return Ok(None),
+        };
 
         let rey_count = round
             .iter_from_hand()
             .filter(|&(team, _seat)| team == winner_team)
             .flat_map(|(_team, seat)| &seat.face_up_cards)
             .filter(|&card| card.value == deck::Value::Rey)
-            .count() as u8;
+            .count() as scoreboard::Points;
 
-        Some(scoreboard::RoundScoreSection(winner_team, rey_count))
+        Ok(Some(scoreboard::RoundScoreSection(winner_team, rey_count)))
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use cardset::CardSet;
     use Game;
     use Player;
     use Seat;
@@ -41,11 +43,11 @@ mod tests {
     #[test]
     fn get_rey_score_no_reyes() {
         let game = Game::new(vec![Player::new("a")]);
-        let mut round = Round::new(&game, &game.players[0], deck::Deck::default());
+        let mut round = Round::new(&game, &game.players[0], deck::Deck::default()).unwrap();
 
         round.seats = vec![Seat {
             player: &game.players[0],
-            hand: vec![],
+            hand: CardSet::empty(),
             face_up_cards: vec![
                 deck::Card {
                     suit: deck::Suit::Oros,
@@ -62,7 +64,7 @@ mod tests {
             ],
         }];
 
-        let result = ReyScorer::default().get_score(&round);
+        let result = ReyScorer.get_score(&round).unwrap();
         let expected = None;
 
         assert_eq!(result, expected);
@@ -71,11 +73,11 @@ mod tests {
     #[test]
     fn get_rey_score_one_rey() {
         let game = Game::new(vec![Player::new("a")]);
-        let mut round = Round::new(&game, &game.players[0], deck::Deck::default());
+        let mut round = Round::new(&game, &game.players[0], deck::Deck::default()).unwrap();
 
         round.seats = vec![Seat {
             player: &game.players[0],
-            hand: vec![],
+            hand: CardSet::empty(),
             face_up_cards: vec![
                 deck::Card {
                     suit: deck::Suit::Oros,
@@ -92,7 +94,7 @@ mod tests {
             ],
         }];
 
-        let result = ReyScorer::default().get_score(&round);
+        let result = ReyScorer.get_score(&round).unwrap();
         let expected = Some(scoreboard::RoundScoreSection(Team::Team1, 1));
 
         assert_eq!(result, expected);
@@ -101,11 +103,11 @@ mod tests {
     #[test]
     fn get_rey_score_tres_rey() {
         let game = Game::new(vec![Player::new("a")]);
-        let mut round = Round::new(&game, &game.players[0], deck::Deck::default());
+        let mut round = Round::new(&game, &game.players[0], deck::Deck::default()).unwrap();
 
         round.seats = vec![Seat {
             player: &game.players[0],
-            hand: vec![],
+            hand: CardSet::empty(),
             face_up_cards: vec![
                 deck::Card {
                     suit: deck::Suit::Oros,
@@ -122,7 +124,7 @@ mod tests {
             ],
         }];
 
-        let result = ReyScorer::default().get_score(&round);
+        let result = ReyScorer.get_score(&round).unwrap();
         let expected = Some(scoreboard::RoundScoreSection(Team::Team1, 3));
 
         assert_eq!(result, expected);
@@ -131,12 +133,12 @@ mod tests {
     #[test]
     fn get_rey_score_many_in_team() {
         let game = Game::new(vec![Player::new("a"), Player::new("b")]);
-        let mut round = Round::new(&game, &game.players[0], deck::Deck::default());
+        let mut round = Round::new(&game, &game.players[0], deck::Deck::default()).unwrap();
 
         round.seats = vec![
             Seat {
                 player: &game.players[0],
-                hand: vec![],
+                hand: CardSet::empty(),
                 face_up_cards: vec![
                     deck::Card {
                         suit: deck::Suit::Oros,
@@ -154,7 +156,7 @@ mod tests {
             },
             Seat {
                 player: &game.players[0],
-                hand: vec![],
+                hand: CardSet::empty(),
                 face_up_cards: vec![
                     deck::Card {
                         suit: deck::Suit::Oros,
@@ -172,7 +174,7 @@ mod tests {
             },
             Seat {
                 player: &game.players[0],
-                hand: vec![],
+                hand: CardSet::empty(),
                 face_up_cards: vec![
                     deck::Card {
                         suit: deck::Suit::Oros,
@@ -190,7 +192,7 @@ mod tests {
             },
             Seat {
                 player: &game.players[0],
-                hand: vec![],
+                hand: CardSet::empty(),
                 face_up_cards: vec![
                     deck::Card {
                         suit: deck::Suit::Oros,
@@ -208,7 +210,7 @@ mod tests {
             },
         ];
 
-        let result = ReyScorer::default().get_score(&round);
+        let result = ReyScorer.get_score(&round).unwrap();
         let expected = Some(scoreboard::RoundScoreSection(Team::Team2, 2));
 
         assert_eq!(result, expected);
@@ -217,12 +219,12 @@ mod tests {
     #[test]
     fn get_rey_score_many_in_team_at_hand() {
         let game = Game::new(vec![Player::new("a"), Player::new("b")]);
-        let mut round = Round::new(&game, &game.players[1], deck::Deck::default());
+        let mut round = Round::new(&game, &game.players[1], deck::Deck::default()).unwrap();
 
         round.seats = vec![
             Seat {
                 player: &game.players[0],
-                hand: vec![],
+                hand: CardSet::empty(),
                 face_up_cards: vec![
                     deck::Card {
                         suit: deck::Suit::Oros,
@@ -240,7 +242,7 @@ mod tests {
             },
             Seat {
                 player: &game.players[1],
-                hand: vec![],
+                hand: CardSet::empty(),
                 face_up_cards: vec![
                     deck::Card {
                         suit: deck::Suit::Oros,
@@ -258,7 +260,7 @@ mod tests {
             },
             Seat {
                 player: &game.players[0],
-                hand: vec![],
+                hand: CardSet::empty(),
                 face_up_cards: vec![
                     deck::Card {
                         suit: deck::Suit::Oros,
@@ -276,7 +278,7 @@ mod tests {
             },
         ];
 
-        let result = ReyScorer::default().get_score(&round);
+        let result = ReyScorer.get_score(&round).unwrap();
         let expected = Some(scoreboard::RoundScoreSection(Team::Team1, 4));
 
         assert_eq!(result, expected);
@@ -1,31 +1,42 @@
 use super::Scorer;
 use hands::{secansa, Hand};
 use scoreboard;
+use Error;
+use Result;
 use Round;
 use Team;
 
 #[derive(Debug, Copy, Clone, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub enum AgreedBet {
     Announced(Option<Team>),
     Envit(Option<Team>),
     Val(u8, Option<Team>),
 }
 
-#[derive(Default)]
+#[derive(Default, Clone)]
 pub struct SecansaScorer {
     agreed_bet: Option<AgreedBet>,
 }
 
 impl SecansaScorer {
-    pub fn set_bet(&mut self, agreed_bet: AgreedBet) {
+    pub fn set_bet(&mut self, agreed_bet: AgreedBet) -> Result<()> {
+        // A val is worth one point less than its number, so there is no zero val
+        if let AgreedBet::Val(0, _) = agreed_bet {
+            return Err(Error::IllegalBet);
+        }
         self.agreed_bet = Some(agreed_bet);
+        Ok(())
     }
 }
 
 impl Scorer for SecansaScorer {
-    fn get_score(&self, round: &Round) -> Option<scoreboard::RoundScoreSection> {
+    fn get_score(&self, round: &Round) -> Result<Option<scoreboard::RoundScoreSection>> {
         // Game must've been announced to be scored
-        let game_bet = self.agreed_bet?;
+        let game_bet = match self.agreed_bet {
+            Some(game_bet) => game_bet,
+            None => return Ok(None),
+        };
 
         let bet_winner = match game_bet {
             | AgreedBet::Announced(winner)
@@ -33,7 +44,12 @@ impl Scorer for SecansaScorer {
             | AgreedBet::Val(_, winner) => winner,
         };
 
-        let cards_winner = round.get_winner_from_cards::<secansa::Secansa>();
+        // The joc registered under the name, which house rules may have replaced
+        let joc = match round.game.jocs().get(secansa::Secansa::NAME) {
+            Some(joc) => joc,
+            None => return Ok(None),
+        };
+        let cards_winner = joc.winner(round);
 
         let winner = match (bet_winner, cards_winner) {
             // If there was a rejected bet, we've got a direct winner
@@ -41,33 +57,30 @@ impl Scorer for SecansaScorer {
             // Otherwise get the winner from the facing up cards
             (None, Some(winner)) => winner,
             // If there still no winner, we can't score anything
-            _ => return None,
+            _ => return Ok(None),
         };
 
-        let games_value: u8 = round
-            .seats
-            .iter()
-            .enumerate()
-            .filter(|&(pos, seat)| seat.get_team(pos as u8) == winner)
-            .filter_map(|(_, seat)| secansa::Secansa::from_cards(&seat.face_up_cards, round.marker))
-            .map(|secansa| secansa.score())
-            .sum();
+        let games_value = joc.team_score(round, winner)?;
 
         let extra = match game_bet {
             AgreedBet::Envit(_) => 1,
-            AgreedBet::Val(extra, _) => extra - 1, // e.g.: tres val gives 2 points
+            // e.g.: tres val gives 2 points
+            AgreedBet::Val(extra, _) => extra.checked_sub(1).ok_or(Error::IllegalBet)?,
             _ => 0,
         };
 
-        let total = games_value + extra;
+        let total = games_value
+            .checked_add(scoreboard::Points::from(extra))
+            .ok_or(Error::ScoreOverflow)?;
 
-        Some(scoreboard::RoundScoreSection(winner, total))
+        Ok(Some(scoreboard::RoundScoreSection(winner, total)))
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use cardset::CardSet;
     use deck;
     use Game;
     use Player;
@@ -80,20 +93,32 @@ mod tests {
 
         assert!(secansa_scorer.agreed_bet.is_none());
 
-        secansa_scorer.set_bet(bet);
+        secansa_scorer.set_bet(bet).unwrap();
 
         assert!(secansa_scorer.agreed_bet.is_some());
 
         assert_eq!(secansa_scorer.agreed_bet, Some(bet));
     }
 
-    fn secansa_tests_round_fixture(game: &Game) -> Round {
-        let mut round = Round::new(game, &game.players[0], deck::Deck::default());
+    #[test]
+    fn test_set_illegal_bet() {
+        let mut secansa_scorer = SecansaScorer::default();
+        let result = secansa_scorer.set_bet(AgreedBet::Val(0, None));
+
+        assert_eq!(result, Err(Error::IllegalBet));
+        assert!(secansa_scorer.agreed_bet.is_none());
+
+        // Any other val is up to the players
+        assert_eq!(secansa_scorer.set_bet(AgreedBet::Val(2, None)), Ok(()));
+    }
+
+    fn secansa_tests_round_fixture(game: &Game) -> Round<'_> {
+        let mut round = Round::new(game, &game.players[0], deck::Deck::default()).unwrap();
 
         round.seats = vec![
             Seat {
                 player: &game.players[0],
-                hand: vec![],
+                hand: CardSet::empty(),
                 face_up_cards: vec![
                     deck::Card {
                         suit: deck::Suit::Oros,
@@ -111,7 +136,7 @@ mod tests {
             },
             Seat {
                 player: &game.players[0],
-                hand: vec![],
+                hand: CardSet::empty(),
                 face_up_cards: vec![
                     deck::Card {
                         suit: deck::Suit::Oros,
@@ -129,7 +154,7 @@ mod tests {
             },
             Seat {
                 player: &game.players[0],
-                hand: vec![],
+                hand: CardSet::empty(),
                 face_up_cards: vec![
                     deck::Card {
                         suit: deck::Suit::Oros,
@@ -147,7 +172,7 @@ mod tests {
             },
             Seat {
                 player: &game.players[0],
-                hand: vec![],
+                hand: CardSet::empty(),
                 face_up_cards: vec![
                     deck::Card {
                         suit: deck::Suit::Copas,
@@ -174,17 +199,17 @@ mod tests {
         let round = secansa_tests_round_fixture(&game);
         let secansa_scorer = SecansaScorer::default();
 
-        assert!(secansa_scorer.get_score(&round).is_none())
+        assert!(secansa_scorer.get_score(&round).unwrap().is_none())
     }
 
     #[test]
     fn get_secansa_score_announced_no_secansa() {
         // This situation should be impossible! Testing as it can be done in code anyway
         let game = Game::new(vec![Player::new("a"), Player::new("b")]);
-        let mut round = Round::new(&game, &game.players[0], deck::Deck::default());
+        let mut round = Round::new(&game, &game.players[0], deck::Deck::default()).unwrap();
 
         let mut secansa_scorer = SecansaScorer::default();
-        secansa_scorer.set_bet(AgreedBet::Envit(None));
+        secansa_scorer.set_bet(AgreedBet::Envit(None)).unwrap();
 
         round.seats = vec![
             // No secansa
@@ -200,15 +225,15 @@ mod tests {
                         value: deck::Value::Tres,
                     },
                     deck::Card {
-                        suit: round.marker.suit,
-                        value: deck::Value::Caballo,
+                        suit: deck::Suit::Copas,
+                        value: deck::Value::Cinco,
                     },
                 ],
-                hand: vec![],
+                hand: CardSet::empty(),
             },
         ];
 
-        assert!(secansa_scorer.get_score(&round).is_none())
+        assert!(secansa_scorer.get_score(&round).unwrap().is_none())
     }
 
     #[test]
@@ -217,10 +242,12 @@ mod tests {
         let round = secansa_tests_round_fixture(&game);
 
         let mut secansa_scorer = SecansaScorer::default();
-        secansa_scorer.set_bet(AgreedBet::Announced(Some(Team::Team1)));
+        secansa_scorer
+            .set_bet(AgreedBet::Announced(Some(Team::Team1)))
+            .unwrap();
 
         let expected = Some(scoreboard::RoundScoreSection(Team::Team1, 4));
-        assert_eq!(secansa_scorer.get_score(&round), expected);
+        assert_eq!(secansa_scorer.get_score(&round).unwrap(), expected);
     }
 
     #[test]
@@ -229,10 +256,10 @@ mod tests {
         let round = secansa_tests_round_fixture(&game);
 
         let mut secansa_scorer = SecansaScorer::default();
-        secansa_scorer.set_bet(AgreedBet::Announced(None));
+        secansa_scorer.set_bet(AgreedBet::Announced(None)).unwrap();
 
         let expected = Some(scoreboard::RoundScoreSection(Team::Team2, 6));
-        assert_eq!(secansa_scorer.get_score(&round), expected);
+        assert_eq!(secansa_scorer.get_score(&round).unwrap(), expected);
     }
 
     #[test]
@@ -241,10 +268,12 @@ mod tests {
         let round = secansa_tests_round_fixture(&game);
 
         let mut secansa_scorer = SecansaScorer::default();
-        secansa_scorer.set_bet(AgreedBet::Envit(Some(Team::Team1)));
+        secansa_scorer
+            .set_bet(AgreedBet::Envit(Some(Team::Team1)))
+            .unwrap();
 
         let expected = Some(scoreboard::RoundScoreSection(Team::Team1, 5));
-        assert_eq!(secansa_scorer.get_score(&round), expected);
+        assert_eq!(secansa_scorer.get_score(&round).unwrap(), expected);
     }
 
     #[test]
@@ -253,10 +282,10 @@ mod tests {
         let round = secansa_tests_round_fixture(&game);
 
         let mut secansa_scorer = SecansaScorer::default();
-        secansa_scorer.set_bet(AgreedBet::Envit(None));
+        secansa_scorer.set_bet(AgreedBet::Envit(None)).unwrap();
 
         let expected = Some(scoreboard::RoundScoreSection(Team::Team2, 7));
-        assert_eq!(secansa_scorer.get_score(&round), expected);
+        assert_eq!(secansa_scorer.get_score(&round).unwrap(), expected);
     }
 
     #[test]
@@ -265,10 +294,12 @@ mod tests {
         let round = secansa_tests_round_fixture(&game);
 
         let mut secansa_scorer = SecansaScorer::default();
-        secansa_scorer.set_bet(AgreedBet::Val(3, Some(Team::Team1)));
+        secansa_scorer
+            .set_bet(AgreedBet::Val(3, Some(Team::Team1)))
+            .unwrap();
 
         let expected = Some(scoreboard::RoundScoreSection(Team::Team1, 6));
-        assert_eq!(secansa_scorer.get_score(&round), expected);
+        assert_eq!(secansa_scorer.get_score(&round).unwrap(), expected);
     }
 
     #[test]
@@ -277,9 +308,21 @@ mod tests {
         let round = secansa_tests_round_fixture(&game);
 
         let mut secansa_scorer = SecansaScorer::default();
-        secansa_scorer.set_bet(AgreedBet::Val(3, None));
+        secansa_scorer.set_bet(AgreedBet::Val(3, None)).unwrap();
 
         let expected = Some(scoreboard::RoundScoreSection(Team::Team2, 8));
-        assert_eq!(secansa_scorer.get_score(&round), expected);
+        assert_eq!(secansa_scorer.get_score(&round).unwrap(), expected);
+    }
+
+    #[test]
+    fn get_secansa_score_zero_val() {
+        let game = Game::new(vec![Player::new("a"), Player::new("b")]);
+        let round = secansa_tests_round_fixture(&game);
+
+        // set_bet refuses it, but the scorer must not underflow either
+        let secansa_scorer = SecansaScorer {
+            agreed_bet: Some(AgreedBet::Val(0, Some(Team::Team1))),
+        };
+        assert_eq!(secansa_scorer.get_score(&round), Err(Error::IllegalBet));
     }
 }
@@ -1,11 +1,14 @@
 use super::Scorer;
 use deck::{self, Card, Suit, Value};
 use scoreboard;
+use std::cmp::Reverse;
+use Play;
+use Result;
 use Round;
 use Team;
 
-#[derive(PartialEq, Eq, PartialOrd, Ord, Copy, Clone)]
-enum TrucValue {
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Copy, Clone)]
+pub enum TrucValue {
     Cuatro,
     Cinco,
     Seis,
@@ -84,27 +87,70 @@ impl TrucValue {
     }
 }
 
-#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub fn truc_rank(card: Card, marker: Card) -> TrucValue {
+    TrucValue::new(card, marker)
+}
+
+pub fn beats(card: Card, other: Card, marker: Card) -> bool {
+    //! Whether the card wins a baza against the other one. Cards of the same rank never beat each
+    //! other, a baza with no higher card than those is parda.
+
+    truc_rank(card, marker) > truc_rank(other, marker)
+}
+
+pub fn cards_in_truc_order(marker: Card) -> impl Iterator<Item = Card> {
+    //! All 40 cards from highest to lowest truc rank, cards of the same rank in deck order.
+
+    let mut cards = deck::all_cards().collect::<Vec<_>>();
+    cards.sort_by_key(|&card| Reverse(truc_rank(card, marker)));
+    cards.into_iter()
+}
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub enum Bet {
+    #[default]
     None,
+    // If the call was declined, the team that made it wins the truc
     Truc(Option<Team>),
     Retruc(Option<Team>),
-    NouVal,
-}
-
-impl Default for Bet {
-    fn default() -> Bet {
-        Bet::None
-    }
+    NouVal(Option<Team>),
 }
 
 impl Bet {
     fn get_score(self) -> u8 {
         match self {
-            Bet::None => 1,
-            Bet::Truc(_) => 3,
-            Bet::Retruc(_) => 6,
-            Bet::NouVal => 9,
+            Bet::None | Bet::Truc(Some(_)) => 1,
+            Bet::Truc(None) | Bet::Retruc(Some(_)) => 3,
+            Bet::Retruc(None) | Bet::NouVal(Some(_)) => 6,
+            Bet::NouVal(None) => 9,
+        }
+    }
+
+    pub fn winner(self) -> Option<Team> {
+        //! The team that won the truc because the other one declined the call, if any.
+
+        match self {
+            Bet::None => None,
+            Bet::Truc(winner) | Bet::Retruc(winner) | Bet::NouVal(winner) => winner,
+        }
+    }
+
+    pub(crate) fn raise(self) -> Option<Bet> {
+        match self {
+            Bet::None => Some(Bet::Truc(None)),
+            Bet::Truc(None) => Some(Bet::Retruc(None)),
+            Bet::Retruc(None) => Some(Bet::NouVal(None)),
+            _ => None,
+        }
+    }
+
+    pub(crate) fn decline(self, winner: Team) -> Bet {
+        match self {
+            Bet::None => Bet::None,
+            Bet::Truc(_) => Bet::Truc(Some(winner)),
+            Bet::Retruc(_) => Bet::Retruc(Some(winner)),
+            Bet::NouVal(_) => Bet::NouVal(Some(winner)),
         }
     }
 }
@@ -138,37 +184,53 @@ impl Baza {
         Baza { cards: baza_cards }
     }
 
-    fn winner(&self) -> BazaWinner {
-        // If there's no cards it is automatically Parda
-        if self.cards.is_empty() {
-            return BazaWinner::Parda;
-        }
-
-        let mut truc_cards = self.cards.clone();
-        truc_cards.sort_by_key(|baza_card| baza_card.card);
-        truc_cards.reverse();
+    fn from_plays(plays: &[Play], round: &Round) -> Baza {
+        let cards = plays
+            .iter()
+            .map(|play| {
+                let team = round
+                    .seat_team(play.seat)
+                    .expect("Cards are only played from a seat");
+                (play.card, team)
+            })
+            .collect::<Vec<_>>();
+        Baza::new(&cards, round.marker)
+    }
 
-        let highest_card = truc_cards[0].card;
+    fn highest(&self) -> Option<usize> {
+        //! Position of the highest card, the first one played among those of the same rank.
 
-        // Get the highest cards and check the teams.
-        // If only one, that's the winner. Otherwise there is Parda
-        let mut high_card_teams = truc_cards
+        // Iterator::max_by_key returns the last element that matches. We need the first.
+        self.cards
             .iter()
-            .filter(|baza_card| baza_card.card == highest_card)
-            .map(|baza_card| baza_card.team)
-            .collect::<Vec<_>>();
+            .enumerate()
+            .rev()
+            .max_by_key(|(_, baza_card)| baza_card.card)
+            .map(|(position, _)| position)
+    }
+
+    fn winner(&self) -> BazaWinner {
+        // If there's no cards it is automatically Parda
+        let highest = match self.highest() {
+            Some(position) => self.cards[position],
+            None => return BazaWinner::Parda,
+        };
 
-        high_card_teams.dedup();
+        // A card of the same rank from the other team makes it Parda
+        let parda = self
+            .cards
+            .iter()
+            .any(|baza_card| baza_card.card == highest.card && baza_card.team != highest.team);
 
-        match high_card_teams.as_slice() {
-            [Team::Team1] => BazaWinner::Team1,
-            [Team::Team2] => BazaWinner::Team2,
-            _ => BazaWinner::Parda,
+        match highest.team {
+            _ if parda => BazaWinner::Parda,
+            Team::Team1 => BazaWinner::Team1,
+            Team::Team2 => BazaWinner::Team2,
         }
     }
 }
 
-#[derive(Default)]
+#[derive(Default, Clone)]
 pub struct TrucScorer {
     agreed_bet: Bet,
 }
@@ -180,48 +242,31 @@ impl TrucScorer {
 }
 
 impl Scorer for TrucScorer {
-    fn get_score(&self, round: &Round) -> Option<scoreboard::RoundScoreSection> {
-        // Create bazas
-
-        // Find the seat with the most shown cards
-        let max_cards = round
-            .seats
+    fn get_score(&self, round: &Round) -> Result<Option<scoreboard::RoundScoreSection>> {
+        let bazas = round
+            .bazas()
             .iter()
-            .map(|seat| seat.face_up_cards.len())
-            .max();
+            .map(|plays| Baza::from_plays(plays, round).winner())
+            .collect::<Vec<_>>();
 
-        // If no players have been sat we can't compute any score
-        let max_cards = match max_cards {
-            Some(max_cards) => max_cards,
-            None => return None,
+        // A declined call decides the truc regardless of the bazas
+        let winner = match self.agreed_bet.winner() {
+            Some(winner) => Some(winner),
+            None => get_truc_winner(&bazas),
         };
 
-        // Iterate that seat while taking cards on the same position on other seats (our Bazas)
-        let bazas = (0..max_cards)
-            .map(|i| {
-                round
-                    .seats
-                    .iter()
-                    .enumerate()
-                    .filter_map(|(pos, seat)| {
-                        if let Some(card) = seat.face_up_cards.get(i) {
-                            Some((*card, seat.get_team(pos as u8)))
-                        } else {
-                            None
-                        }
-                    })
-                    .collect::<Vec<(Card, Team)>>()
-            })
-            .map(|baza_cards| Baza::new(baza_cards.as_slice(), round.marker))
-            .map(|baza| baza.winner())
-            .collect::<Vec<_>>();
+        let winner_score = scoreboard::Points::from(self.agreed_bet.get_score());
 
-        let winner = get_truc_winner(&bazas);
+        Ok(winner.map(|winner| scoreboard::RoundScoreSection(winner, winner_score)))
+    }
+}
 
-        let winner_score = self.agreed_bet.get_score();
+pub(crate) fn winning_play(baza: &[Play], round: &Round) -> Option<Play> {
+    //! The play that takes the baza, the first one played in case of parda.
 
-        winner.map(|winner| scoreboard::RoundScoreSection(winner, winner_score))
-    }
+    Baza::from_plays(baza, round)
+        .highest()
+        .map(|position| baza[position])
 }
 
 fn get_truc_winner(bazas: &[BazaWinner]) -> Option<Team> {
@@ -247,7 +292,7 @@ fn get_truc_winner(bazas: &[BazaWinner]) -> Option<Team> {
                 None
             }
         })
-        .nth(0);
+        .next();
 
     // Special case: Team1 wins baza 1, Team2 wins baza 2, then Parda
     // In this case the winner is whoever won the first baza
@@ -270,13 +315,54 @@ fn get_truc_winner(bazas: &[BazaWinner]) -> Option<Team> {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use cardset::CardSet;
     use test_runner;
+    use Game;
+    use Player;
+    use Seat;
 
     #[test]
     fn compare_truc_value() {
         assert!(TrucValue::AsEspadas > TrucValue::Cinco);
     }
 
+    #[test]
+    fn truc_rank_and_beats() {
+        let marker = "4O".parse().unwrap();
+        let perico = "CO".parse().unwrap();
+        let as_espadas = "1E".parse().unwrap();
+        assert_eq!(truc_rank(perico, marker), TrucValue::Perico);
+        assert_eq!(
+            truc_rank("7B".parse().unwrap(), marker),
+            TrucValue::SieteBobo
+        );
+
+        assert!(beats(perico, as_espadas, marker));
+        assert!(!beats(as_espadas, perico, marker));
+        // Same rank, neither beats the other
+        for &(card, other) in &[("3C", "3B"), ("5C", "5B"), ("RB", "RE"), ("2O", "2C")] {
+            let card = card.parse().unwrap();
+            let other = other.parse().unwrap();
+            assert_eq!(truc_rank(card, marker), truc_rank(other, marker));
+            assert!(!beats(card, other, marker));
+            assert!(!beats(other, card, marker));
+        }
+    }
+
+    #[test]
+    fn cards_in_truc_order_for_marker() {
+        // With a caballo marker, the rey is the perico
+        let marker = "CE".parse().unwrap();
+        let cards = cards_in_truc_order(marker).collect::<Vec<_>>();
+
+        assert_eq!(cards.len(), 40);
+        assert_eq!(cards[..4], deck::parse_cards("RE SE 1E 1B").unwrap()[..]);
+        assert_eq!(cards[39].value, Value::Cuatro);
+        assert!(cards
+            .windows(2)
+            .all(|pair| !beats(pair[1], pair[0], marker)));
+    }
+
     #[test]
     fn get_baza_winner_no_cards() {
         let baza = Baza::new(
@@ -423,6 +509,100 @@ mod tests {
         assert_eq!(BazaWinner::Team2, baza.winner());
     }
 
+    #[test]
+    fn winning_play_parda() {
+        let game = Game::new(vec![Player::new("a"), Player::new("b")]);
+        let round = Round::new(&game, &game.players[0], deck::Deck::default()).unwrap();
+        let baza = [
+            Play {
+                seat: 1,
+                card: "3B".parse().unwrap(),
+            },
+            Play {
+                seat: 0,
+                card: "3C".parse().unwrap(),
+            },
+        ];
+
+        // The baza is parda, the first of the highest cards leads the next one
+        assert_eq!(Baza::from_plays(&baza, &round).winner(), BazaWinner::Parda);
+        assert_eq!(winning_play(&baza, &round), Some(baza[0]));
+    }
+
+    #[test]
+    #[cfg(feature = "serde")]
+    fn bet_serde() {
+        use serde_json;
+
+        let bet = Bet::Retruc(Some(Team::Team2));
+        let json = serde_json::to_string(&bet).unwrap();
+        assert_eq!(json, r#"{"Retruc":"Team2"}"#);
+        assert_eq!(serde_json::from_str::<Bet>(&json).unwrap(), bet);
+    }
+
+    #[test]
+    fn bet_get_score() {
+        assert_eq!(Bet::None.get_score(), 1);
+        assert_eq!(Bet::Truc(None).get_score(), 3);
+        assert_eq!(Bet::Retruc(None).get_score(), 6);
+        assert_eq!(Bet::NouVal(None).get_score(), 9);
+
+        // Declined calls are worth the previous level
+        assert_eq!(Bet::Truc(Some(Team::Team1)).get_score(), 1);
+        assert_eq!(Bet::Retruc(Some(Team::Team1)).get_score(), 3);
+        assert_eq!(Bet::NouVal(Some(Team::Team1)).get_score(), 6);
+    }
+
+    #[test]
+    fn get_truc_score_declined_call() {
+        let game = Game::new(vec![Player::new("a"), Player::new("b")]);
+        let mut round = Round::new(&game, &game.players[0], deck::Deck::default()).unwrap();
+
+        round.seats = vec![
+            Seat {
+                player: &game.players[0],
+                hand: CardSet::empty(),
+                face_up_cards: vec![
+                    Card {
+                        value: Value::Tres,
+                        suit: Suit::Bastos,
+                    },
+                    Card {
+                        value: Value::Tres,
+                        suit: Suit::Copas,
+                    },
+                ],
+            },
+            Seat {
+                player: &game.players[1],
+                hand: CardSet::empty(),
+                face_up_cards: vec![
+                    Card {
+                        value: Value::Cuatro,
+                        suit: Suit::Bastos,
+                    },
+                    Card {
+                        value: Value::Cuatro,
+                        suit: Suit::Copas,
+                    },
+                ],
+            },
+        ];
+        round.play_face_up_cards();
+
+        let mut truc_scorer = TrucScorer::default();
+        assert_eq!(
+            truc_scorer.get_score(&round).unwrap(),
+            Some(scoreboard::RoundScoreSection(Team::Team1, 1))
+        );
+
+        truc_scorer.set_bet(Bet::Retruc(Some(Team::Team2)));
+        assert_eq!(
+            truc_scorer.get_score(&round).unwrap(),
+            Some(scoreboard::RoundScoreSection(Team::Team2, 3))
+        );
+    }
+
     #[test]
     fn get_truc_winner_no_baza() {
         let winner = get_truc_winner(&[]);
@@ -477,7 +657,7 @@ mod tests {
                 expected: Some(Team::Team1),
             },
         ];
-        let runner = test_runner::run(&test_cases, |input, expected| {
+        test_runner::run(&test_cases, |input, expected| {
             let result = get_truc_winner(&input);
             assert_eq!(expected, result);
         });
@@ -534,7 +714,7 @@ mod tests {
                 expected: Some(Team::Team2),
             },
         ];
-        let runner = test_runner::run(&test_cases, |input, expected| {
+        test_runner::run(&test_cases, |input, expected| {
             let result = get_truc_winner(&input);
             assert_eq!(expected, result);
         });
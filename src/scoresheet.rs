@@ -0,0 +1,342 @@
+use scoreboard::{Points, RoundScore, RoundScoreSection, Scoreboard, Section, SECTIONS};
+use Result;
+use Team;
+
+const TEAMS: [Team; 2] = [Team::Team1, Team::Team2];
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum Layout {
+    // One line per round, narrow enough for a terminal
+    Compact,
+    // A column per section and team, for printing
+    Wide,
+}
+
+pub fn render(scoreboard: &Scoreboard, layout: Layout) -> Result<String> {
+    //! Draw the scoreboard like the hand-written sheet: every coto and cama with its rounds and
+    //! running totals, and the camas and cotos won by each team as tally marks.
+
+    let game_config = scoreboard.game_config;
+    let sections = game_config.sections();
+    let mut lines = Vec::new();
+    let mut cotos_won = [0, 0];
+
+    for (coto_index, coto) in scoreboard.cotos().iter().enumerate() {
+        // The coto started after the last round has nothing to show yet
+        if coto.cames().iter().all(|cama| cama.rounds().is_empty()) {
+            continue;
+        }
+        lines.push(format!("Coto {}", coto_index + 1));
+
+        let mut camas_won = [0, 0];
+        for (cama_index, cama) in coto.cames().iter().enumerate() {
+            if cama.rounds().is_empty() {
+                continue;
+            }
+            let title = format!("  Cama {}", cama_index + 1);
+            // Rounds as the overshoot policy scores them
+            let rounds = cama.scored_rounds(game_config)?;
+            let totals = match layout {
+                Layout::Compact => compact_cama(&mut lines, &title, &rounds, &sections),
+                Layout::Wide => wide_cama(&mut lines, &title, &rounds, &sections),
+            };
+            lines.push(match cama.deciding_section(game_config)? {
+                Some((winner, section)) => {
+                    camas_won[team_index(winner)] += 1;
+                    format!(
+                        "{} won by {:?}, {}-{}, on {}",
+                        title,
+                        winner,
+                        totals[0],
+                        totals[1],
+                        section.name()
+                    )
+                }
+                None => format!("{} in play", title),
+            });
+        }
+
+        lines.push(format!("  Camas  {}", tallies(camas_won)));
+        if let Some(winner) = coto.winner(game_config)? {
+            cotos_won[team_index(winner)] += 1;
+            lines.push(format!("Coto {} won by {:?}", coto_index + 1, winner));
+        }
+    }
+
+    lines.push(format!("Cotos  {}", tallies(cotos_won)));
+    if let Some(winner) = scoreboard.winner()? {
+        lines.push(format!("Game won by {:?}", winner));
+    }
+
+    Ok(lines
+        .iter()
+        .map(|line| format!("{}\n", line.trim_end()))
+        .collect())
+}
+
+fn compact_cama(
+    lines: &mut Vec<String>,
+    title: &str,
+    rounds: &[RoundScore],
+    sections: &[Section],
+) -> [Points; 2] {
+    //! One line per round: the sections won by each team, then the running totals.
+
+    lines.push(format!(
+        "{:<10}{:<16}{:<16}{:>8}",
+        title, "Team1", "Team2", "Total"
+    ));
+    let mut totals = [0, 0];
+    for (round_index, round) in rounds.iter().enumerate() {
+        let won = TEAMS
+            .iter()
+            .map(|&team| {
+                sections
+                    .iter()
+                    .filter_map(|&section| match round.section(section) {
+                        Some(RoundScoreSection(winner, points)) if winner == team => {
+                            Some(format!("{}{}", abbreviation(section), points))
+                        }
+                        _ => None,
+                    })
+                    .collect::<Vec<_>>()
+                    .join(" ")
+            })
+            .collect::<Vec<_>>();
+        add_round(&mut totals, round);
+        lines.push(format!(
+            "{:>8}  {:<16}{:<16}{:>4}{:>4}",
+            round_index + 1,
+            won[0],
+            won[1],
+            totals[0],
+            totals[1]
+        ));
+    }
+    totals
+}
+
+fn wide_cama(
+    lines: &mut Vec<String>,
+    title: &str,
+    rounds: &[RoundScore],
+    sections: &[Section],
+) -> [Points; 2] {
+    //! A column per section and team, plus running totals and the cama's points as tally marks.
+
+    let team_width = sections.len() * 5 + 7;
+    lines.push(format!(
+        "{:<10} |{:^w$}|{:^w$}",
+        title,
+        "Team1",
+        "Team2",
+        w = team_width + 1
+    ));
+    let header = sections
+        .iter()
+        .map(|section| format!("{:>5}", column_name(*section)))
+        .collect::<String>();
+    lines.push(format!(
+        "{:>10} |{}{:>7} |{}{:>7}",
+        "Round", header, "Total", header, "Total"
+    ));
+
+    let mut totals = [0, 0];
+    for (round_index, round) in rounds.iter().enumerate() {
+        add_round(&mut totals, round);
+        let mut line = format!("{:>10} |", round_index + 1);
+        for (i, &team) in TEAMS.iter().enumerate() {
+            for &section in sections {
+                match round.section(section) {
+                    Some(RoundScoreSection(winner, points)) if winner == team => {
+                        line.push_str(&format!("{:>5}", points))
+                    }
+                    _ => line.push_str(&format!("{:>5}", ".")),
+                }
+            }
+            line.push_str(&format!("{:>7} |", totals[i]));
+        }
+        lines.push(line.trim_end_matches(" |").to_string());
+    }
+    for (i, team) in TEAMS.iter().enumerate() {
+        lines.push(format!(
+            "{:>10} | {}",
+            format!("{:?}", team),
+            tally(totals[i])
+        ));
+    }
+    totals
+}
+
+fn add_round(totals: &mut [Points; 2], round: &RoundScore) {
+    // Annotated camas never overflow, saturating just keeps the sheet from panicking
+    for &section in &SECTIONS {
+        if let Some(RoundScoreSection(team, points)) = round.section(section) {
+            let total = &mut totals[team_index(team)];
+            *total = total.saturating_add(points);
+        }
+    }
+}
+
+fn team_index(team: Team) -> usize {
+    match team {
+        Team::Team1 => 0,
+        Team::Team2 => 1,
+    }
+}
+
+fn abbreviation(section: Section) -> &'static str {
+    match section {
+        Section::Rey => "R",
+        Section::Flor => "F",
+        Section::Secansa => "S",
+        Section::Ali => "A",
+        Section::Truc => "T",
+    }
+}
+
+fn column_name(section: Section) -> &'static str {
+    match section {
+        Section::Rey => "Rey",
+        Section::Flor => "Flor",
+        Section::Secansa => "Sec",
+        Section::Ali => "Ali",
+        Section::Truc => "Truc",
+    }
+}
+
+fn tally(count: Points) -> String {
+    //! Tally marks in groups of five, the fifth one crossing the other four: `||||/ ||`
+
+    let mut groups = vec!["||||/"; count as usize / 5].join(" ");
+    let rest = count as usize % 5;
+    if rest > 0 {
+        if !groups.is_empty() {
+            groups.push(' ');
+        }
+        groups.push_str(&"|".repeat(rest));
+    }
+    if groups.is_empty() {
+        groups.push('-');
+    }
+    groups
+}
+
+fn tallies(wins: [Points; 2]) -> String {
+    format!("Team1 {}  Team2 {}", tally(wins[0]), tally(wins[1]))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use scoreboard::GameConfig;
+
+    fn round(
+        rey: Option<RoundScoreSection>,
+        ali: Option<RoundScoreSection>,
+        truc: Option<RoundScoreSection>,
+    ) -> RoundScore {
+        RoundScore {
+            rey,
+            flor: None,
+            secansa: None,
+            ali,
+            truc,
+        }
+    }
+
+    fn scoreboard() -> Scoreboard {
+        let mut scoreboard: Scoreboard = Default::default();
+        scoreboard.game_config = GameConfig {
+            cama_win_score: 12,
+            coto_win_score: 2,
+            game_win_score: 1,
+            ..GameConfig::default()
+        };
+        let team1 = |points| Some(RoundScoreSection(Team::Team1, points));
+        let team2 = |points| Some(RoundScoreSection(Team::Team2, points));
+        for &score in &[
+            round(team1(1), team2(2), team1(3)),
+            round(None, team1(5), team1(6)),
+            round(team2(2), None, team2(1)),
+            round(None, None, team1(12)),
+        ] {
+            scoreboard.annotate(score).unwrap();
+        }
+        scoreboard
+    }
+
+    #[test]
+    fn tally_marks() {
+        assert_eq!(tally(0), "-");
+        assert_eq!(tally(3), "|||");
+        assert_eq!(tally(5), "||||/");
+        assert_eq!(tally(12), "||||/ ||||/ ||");
+    }
+
+    #[test]
+    fn render_empty() {
+        let scoreboard: Scoreboard = Default::default();
+        assert_eq!(
+            render(&scoreboard, Layout::Compact).unwrap(),
+            "Cotos  Team1 -  Team2 -\n"
+        );
+    }
+
+    #[test]
+    fn render_compact() {
+        let expected = [
+            "Coto 1",
+            "  Cama 1  Team1           Team2              Total",
+            "       1  R1 T3           A2                 4   2",
+            "       2  A5 T6                             15   2",
+            "  Cama 1 won by Team1, 15-2, on truc",
+            "  Cama 2  Team1           Team2              Total",
+            "       1                  R2 T1              0   3",
+            "       2  T12                               12   3",
+            "  Cama 2 won by Team1, 12-3, on truc",
+            "  Camas  Team1 ||  Team2 -",
+            "Coto 1 won by Team1",
+            "Cotos  Team1 |  Team2 -",
+            "Game won by Team1",
+            "",
+        ];
+        assert_eq!(
+            render(&scoreboard(), Layout::Compact).unwrap(),
+            expected.join("\n")
+        );
+    }
+
+    #[test]
+    fn render_wide() {
+        let sheet = render(&scoreboard(), Layout::Wide).unwrap();
+        let lines = sheet.lines().collect::<Vec<_>>();
+        assert_eq!(
+            lines[1..6],
+            [
+                "  Cama 1   |              Team1              |              Team2",
+                "     Round |  Rey Flor  Sec  Ali Truc  Total |  Rey Flor  Sec  Ali Truc  Total",
+                "         1 |    1    .    .    .    3      4 |    .    .    .    2    .      2",
+                "         2 |    .    .    .    5    6     15 |    .    .    .    .    .      2",
+                "     Team1 | ||||/ ||||/ ||||/",
+            ]
+        );
+        assert_eq!(lines[6], "     Team2 | ||");
+        assert_eq!(lines.last(), Some(&"Game won by Team1"));
+
+        // An unfinished cama, with the sections in the configured order
+        let mut scoreboard: Scoreboard = Default::default();
+        scoreboard.game_config.section_order = [Section::Truc; 5];
+        scoreboard
+            .annotate(round(None, Some(RoundScoreSection(Team::Team2, 2)), None))
+            .unwrap();
+        let sheet = render(&scoreboard, Layout::Wide).unwrap();
+        let lines = sheet.lines().collect::<Vec<_>>();
+        assert_eq!(
+            lines[2],
+            "     Round | Truc  Rey Flor  Sec  Ali  Total | Truc  Rey Flor  Sec  Ali  Total"
+        );
+        assert_eq!(lines[6], "  Cama 1 in play");
+        assert_eq!(lines[7], "  Camas  Team1 -  Team2 -");
+    }
+}